@@ -4,17 +4,180 @@
 pub const PHYSICS_TIMESTEP: f32 = 0.02; // 50Hz physics simulation (1/50 = 0.02)
 pub const GRAVITY: f32 = -9.81;
 
+// Collision group bits (Rapier2D InteractionGroups memberships/filters)
+pub const COLLISION_GROUP_TERRAIN: u32 = 0b0001;
+pub const COLLISION_GROUP_CHARACTER: u32 = 0b0010;
+pub const COLLISION_GROUP_PROJECTILE: u32 = 0b0100;
+pub const COLLISION_GROUP_ITEM: u32 = 0b1000;
+
 
 // Player platformer constants
 pub const START_PLAYER_MASS: u32 = 15;
 pub const PLAYER_MOVE_SPEED: f32 = 5.0;  // Horizontal movement speed
 pub const PLAYER_JUMP_FORCE: f32 = 8.0;  // Jump impulse strength
 
+// Input command queue
+pub const INPUT_COMMAND_QUEUE_CAPACITY: usize = 16;  // Oldest commands are dropped beyond this
+pub const INPUT_COMMAND_MAX_AGE_MS: u64 = 500;       // Commands older than this are discarded as stale
+
+// Input rate limiting (token bucket per identity)
+pub const INPUT_RATE_LIMIT_BURST: f32 = 20.0;        // Bucket capacity
+pub const INPUT_RATE_LIMIT_PER_SECOND: f32 = 60.0;   // Refill rate - clients normally send at 20Hz
+pub const SUSPICIOUS_VIOLATION_THRESHOLD: u32 = 20;  // Violations per window before an identity is flagged
+
+// Chat
+pub const CHAT_MAX_LENGTH: usize = 120;              // Characters per message
+pub const CHAT_RATE_LIMIT_BURST: f32 = 5.0;
+pub const CHAT_RATE_LIMIT_PER_SECOND: f32 = 1.0;
+pub const MEGAPHONE_RATE_LIMIT_BURST: f32 = 1.0;
+pub const MEGAPHONE_RATE_LIMIT_PER_SECOND: f32 = 1.0 / 30.0;
+pub const FRIEND_REQUEST_RATE_LIMIT_BURST: f32 = 5.0;
+pub const FRIEND_REQUEST_RATE_LIMIT_PER_SECOND: f32 = 1.0 / 10.0;
+pub const CHAT_RETENTION_MS: u64 = 60_000;
+pub const CHAT_CLEANUP_INTERVAL_MS: u64 = 10_000;
+pub const CHAT_BLOCKED_WORDS: &[&str] = &["hack", "scam"];
+
+// Projectile constants
+pub const PROJECTILE_MASS: u32 = 1;
+pub const ARROW_SPEED: f32 = 18.0;
+pub const ARROW_RADIUS: f32 = 0.1;
+pub const ARROW_GRAVITY_SCALE: f32 = 0.1;   // Slight drop over long flights
+pub const THROWING_STAR_SPEED: f32 = 16.0;
+pub const THROWING_STAR_RADIUS: f32 = 0.15;
+pub const MAGIC_BOLT_SPEED: f32 = 12.0;
+pub const MAGIC_BOLT_RADIUS: f32 = 0.25;
+pub const PROJECTILE_LIFETIME_MS: u64 = 1500;
+
+// Non-player entity defaults
+pub const MONSTER_MASS: u32 = 15;
+pub const MONSTER_SPAWN_MAX_DROP: f32 = 50.0;   // How far below a spawn anchor to look for a platform
+pub const CHARACTER_HALF_HEIGHT: f32 = 0.8;     // Capsule half height (0.5) + radius (0.3)
+pub const CHARACTER_RADIUS: f32 = 0.3;
+pub const EDGE_PROBE_DEPTH: f32 = 0.5;          // How far below the feet still counts as ground ahead
+
+// Navigation graph
+pub const NAV_EDGE_MARGIN: f32 = 0.4;       // Keep departure/landing points this far inside platform edges
+pub const NAV_SAME_HEIGHT: f32 = 0.1;       // Height difference treated as level ground
+pub const NAV_WALK_GAP: f32 = 0.3;          // Gaps up to this wide can simply be walked over
+pub const NAV_JUMP_SAFETY: f32 = 0.9;       // Fraction of theoretical jump height/reach an agent relies on
+pub const NAV_JUMP_PENALTY: f32 = 2.0;      // Extra path cost for jumps, preferring walking routes
+pub const NAV_CLIMB_PENALTY: f32 = 3.0;
+pub const NAV_DEPARTURE_TOLERANCE: f32 = 0.3; // How close to the departure point before taking a link
+
+// Monster AI
+pub const MONSTER_EDGE_LOOKAHEAD: f32 = 0.6;        // Distance ahead to probe for the platform edge
+pub const MONSTER_IDLE_MS: (u64, u64) = (1000, 3000);
+pub const MONSTER_PATROL_MS: (u64, u64) = (2000, 4000);
+pub const MONSTER_CHASE_STOP_DISTANCE: f32 = 0.5;   // Stop closing in once this near horizontally
+pub const MONSTER_CHASE_JUMP_HEIGHT: f32 = 1.0;     // Jump when the target is this much higher
+pub const MONSTER_RETURN_DISTANCE: f32 = 1.0;       // Considered home once this near
+pub const NPC_MASS: u32 = 15;
+pub const ITEM_DROP_MASS: u32 = 1;
+pub const ITEM_DROP_HALF_SIZE: f32 = 0.25;
+
+// Character progression
+pub const MAX_LEVEL: u32 = 200;
+pub const STARTING_STAT: u32 = 4;               // Initial STR/DEX/INT/LUK
+pub const STARTING_AP: u32 = 9;
+pub const AP_PER_LEVEL: u32 = 5;
+pub const SP_PER_LEVEL: u32 = 3;
+pub const HP_PER_LEVEL: u32 = 12;               // Beginner growth; other jobs define their own
+pub const MP_PER_LEVEL: u32 = 6;
+pub const BEGINNER_JOB_ID: u32 = 0;
+pub const SECOND_JOB_QUEST_BASE: u32 = 1000;    // Second job advancement quest id = this + first job id
+pub const MAX_STAT: u32 = 999;
+pub const BASE_SPEED_PERCENT: u32 = 100;
+pub const BASE_JUMP_PERCENT: u32 = 100;
+
+// Combat
+pub const PLAYER_BASE_HP: u32 = 50;             // Max HP/MP of a new character
+pub const PLAYER_BASE_MP: u32 = 5;
+pub const PLAYER_BASE_ATTACK: u32 = 10;
+pub const PLAYER_BASE_DEFENSE: u32 = 0;
+pub const PLAYER_BASE_ACCURACY: u32 = 10;
+pub const PLAYER_BASE_AVOIDABILITY: u32 = 5;
+pub const PLAYER_BASE_CRITICAL_RATE: f32 = 0.05;
+pub const BASE_CRITICAL_DAMAGE: f32 = 1.5;
+pub const DAMAGE_MASTERY: f32 = 0.6;            // Lowest roll as a fraction of attack (maximum is 1.0)
+pub const DEFENSE_REDUCTION: f32 = 0.5;         // Damage removed per point of defense
+pub const MAX_EVADE_CHANCE: f32 = 0.5;          // Evade chance when avoidability dwarfs accuracy
+pub const PLAYER_INVULNERABILITY_MS: u64 = 1500; // I-frames after a player is hit
+pub const KNOCKBACK_SPEED_X: f32 = 4.0;
+pub const KNOCKBACK_SPEED_Y: f32 = 3.0;
+pub const MONSTER_TOUCH_RANGE: (f32, f32) = (0.6, 1.2); // Horizontal/vertical reach of monster body contact
+pub const COMBAT_EVENT_RETENTION_MS: u64 = 5000;  // Damage events are kept this long for clients to read
+
+// Basic attack
+pub const BASIC_ATTACK_COOLDOWN_MS: u64 = 600;
+pub const BASIC_ATTACK_HITBOX_SIZE: (f32, f32) = (1.2, 1.0);  // Width and height of the swing
+pub const BASIC_ATTACK_HITBOX_OFFSET: f32 = 0.7;               // Hitbox center distance in front of the attacker
+pub const BASIC_ATTACK_MAX_TARGETS: usize = 1;                 // Nearest targets first
+
+// Skills
+pub const SKILL_ANIMATION_LOCK_MS: u64 = 600;       // Default lock before the next attack or skill
+pub const PROJECTILE_SPAWN_OFFSET: f32 = 0.5;       // Projectiles appear this far in front of the shooter
+pub const SKILL_PROJECTILE_SPACING: f32 = 0.25;     // Vertical gap between projectiles of one volley
+
+// Status effects
+pub const STATUS_EFFECT_TICK_MS: u64 = 250;         // Expiry check interval
+pub const POISON_TICK_MS: u64 = 1000;
+
+// Mesos and shops
+pub const MAX_MESOS: u64 = 2_147_483_647;
+pub const SHOP_INTERACTION_RANGE: f32 = 3.0;   // How close a player must stand to a shop NPC
+
+// Trading
+pub const TRADE_RANGE: f32 = 5.0;               // Traders further apart than this have their trade cancelled
+pub const TRADE_MAX_ITEMS: usize = 9;           // Stacks each side may offer
+pub const TRADE_INVITE_TIMEOUT_MS: u64 = 30000;
+pub const TRADE_CHECK_MS: u64 = 500;
+
+// Parties
+pub const MAX_PARTY_SIZE: usize = 6;
+pub const PARTY_INVITE_TIMEOUT_MS: u64 = 30000;
+pub const PARTY_REFRESH_MS: u64 = 500;          // How often member HP/map snapshots are updated
+pub const PARTY_EXP_BONUS_PER_MEMBER: f32 = 0.1; // Extra kill EXP per additional member sharing it
+pub const PARTY_EXP_BY_LEVEL: bool = true;      // Split shared EXP by level rather than evenly
+
+// Guilds
+pub const GUILD_CREATION_COST: u64 = 1_500_000;
+pub const GUILD_NAME_LENGTH: (usize, usize) = (3, 12);  // Min and max characters
+pub const GUILD_NOTICE_MAX_LENGTH: usize = 100;
+pub const GUILD_MAX_MEMBERS: usize = 100;
+pub const GUILD_INVITE_TIMEOUT_MS: u64 = 60000;
+pub const GUILD_INVITE_CLEANUP_MS: u64 = 10000; // How often expired invitations are deleted
+pub const GUILD_RANK_TITLE_MAX_LENGTH: usize = 12;
+
+// Friends
+pub const MAX_FRIENDS: usize = 50;                  // Friends plus outgoing requests, so accepted requests always fit
+pub const MAX_INCOMING_FRIEND_REQUESTS: usize = 50;
+
+// Inventory
+pub const INVENTORY_SLOTS_PER_TAB: u32 = 24;
+
+// Loot
+pub const MESO_ITEM_ID: u32 = 0;            // Item id of meso drops; their quantity is the amount
+pub const DROP_OWNERSHIP_MS: u64 = 5000;    // Before anyone but the owner may loot a drop
+pub const DROP_LIFETIME_MS: u64 = 60000;
+pub const DROP_EXPIRY_CHECK_MS: u64 = 1000;
+pub const DROP_POP_SPEED: f32 = 4.0;        // Upward launch speed of fresh drops
+pub const DROP_SPREAD_SPEED: f32 = 1.5;     // Horizontal launch speed between neighbouring drops
+pub const ITEM_PICKUP_RANGE: f32 = 1.0;
+pub const COMMON_POTION_TABLE_ID: u32 = 9000;
+pub const MAX_DROP_TABLE_DEPTH: u32 = 4;     // Nested drop tables below this are ignored
+
 // Game mechanics constants  
 pub const MINIMUM_SAFE_MASS_RATIO: f32 = 0.85;
 
 // World configuration
 pub const DEFAULT_WORLD_SIZE: u64 = 1000;
+pub const DEFAULT_MAP_ID: u32 = 1;
+
+// Area-of-interest grid
+pub const AOI_CELL_SIZE: f32 = 32.0;           // World units per grid cell side
+pub const AOI_VIEW_RADIUS_CELLS: i32 = 1;      // Clients subscribe to this many cells around their own
 
 // Timer intervals (in milliseconds)
 pub const PHYSICS_STEP_INTERVAL_MS: i32 = 20;  // 50Hz physics = 20ms intervals
+pub const SUSPICIOUS_ACTIVITY_WINDOW_MS: u64 = 10_000;  // Violation counting window / flush interval
+pub const MONSTER_SPAWN_CHECK_INTERVAL_MS: u64 = 1000;
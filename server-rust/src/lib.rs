@@ -3,6 +3,7 @@
 
 pub mod tables;
 pub mod reducers;
+pub mod views;
 pub mod utils;
 pub mod config;
 pub mod types;
@@ -11,6 +12,7 @@ pub mod physics; // NEW: Physics simulation module
 // Re-export all modules for external access
 pub use tables::*;
 pub use reducers::*;
+pub use views::*;
 pub use utils::*;
 pub use config::*;
 pub use types::*;
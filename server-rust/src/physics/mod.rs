@@ -1,3 +1,5 @@
 pub mod world;
+pub mod navigation;
 
-pub use world::*;
\ No newline at end of file
+pub use world::*;
+pub use navigation::*;
\ No newline at end of file
@@ -0,0 +1,353 @@
+// Platform navigation graph for monster and NPC pathfinding
+// Nodes are walkable platform segments; links describe how an agent gets from one to another.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use crate::config::*;
+
+// Walkable surface (or ladder) the graph is built from
+#[derive(Clone, Copy, Debug)]
+pub struct NavSurface {
+    pub platform_id: u32,
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,               // Standing height for platforms, top of the ladder for ladders
+    pub bottom: f32,            // Bottom of the ladder; equal to top for platforms
+    pub is_ladder: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NavLinkKind {
+    Walk,   // Surfaces touch at the same height
+    Jump,   // Needs a jump; validated against the agent's jump force and gravity
+    Drop,   // Walk off the edge and fall
+    Climb,  // Ladder or rope between the two surfaces
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NavLink {
+    pub to: usize,
+    pub kind: NavLinkKind,
+    pub departure_x: f32,       // Where to leave the current platform
+    pub landing_x: f32,         // Where the agent should end up on the next platform
+    pub rise: f32,              // Height difference (negative when going down)
+    pub cost: f32,
+}
+
+// What an agent is physically capable of, used to validate links
+#[derive(Clone, Copy, Debug)]
+pub struct NavAgent {
+    pub move_speed: f32,
+    pub jump_force: f32,        // Jump impulse per unit mass, i.e. take-off velocity
+    pub gravity: f32,           // Magnitude of gravity
+    pub can_climb: bool,
+}
+
+// One hop of a path: the link to take from the current platform
+#[derive(Clone, Copy, Debug)]
+pub struct NavStep {
+    pub platform_id: u32,       // Platform the step arrives on
+    pub kind: NavLinkKind,
+    pub departure_x: f32,
+    pub landing_x: f32,
+}
+
+#[derive(Default)]
+pub struct NavGraph {
+    nodes: Vec<NavSurface>,     // Platforms only; ladders become Climb links
+    links: Vec<Vec<NavLink>>,
+}
+
+impl NavAgent {
+    // Highest rise a jump can clear
+    pub fn max_jump_height(&self) -> f32 {
+        self.jump_force * self.jump_force / (2.0 * self.gravity)
+    }
+
+    // Can this agent take the given link?
+    pub fn can_traverse(&self, link: &NavLink) -> bool {
+        let distance = (link.landing_x - link.departure_x).abs();
+        match link.kind {
+            NavLinkKind::Walk => true,
+            NavLinkKind::Climb => self.can_climb,
+            NavLinkKind::Drop => {
+                let fall_time = (2.0 * -link.rise / self.gravity).sqrt();
+                distance <= self.move_speed * fall_time + NAV_EDGE_MARGIN * 2.0
+            }
+            NavLinkKind::Jump => {
+                if self.jump_force <= 0.0 || link.rise > self.max_jump_height() * NAV_JUMP_SAFETY {
+                    return false;
+                }
+                // Time until the arc comes back down to the landing height
+                let v = self.jump_force;
+                let air_time = (v + (v * v - 2.0 * self.gravity * link.rise).max(0.0).sqrt()) / self.gravity;
+                distance <= self.move_speed * air_time * NAV_JUMP_SAFETY
+            }
+        }
+    }
+}
+
+impl NavGraph {
+    pub fn build(surfaces: &[NavSurface]) -> Self {
+        let nodes: Vec<NavSurface> = surfaces.iter().filter(|surface| !surface.is_ladder).copied().collect();
+        let ladders: Vec<NavSurface> = surfaces.iter().filter(|surface| surface.is_ladder).copied().collect();
+        let mut links = vec![Vec::new(); nodes.len()];
+
+        for (from, a) in nodes.iter().enumerate() {
+            for (to, b) in nodes.iter().enumerate() {
+                if from == to {
+                    continue;
+                }
+                if let Some(link) = Self::platform_link(a, b, to) {
+                    links[from].push(link);
+                }
+            }
+
+            // Ladders connect any two platforms whose height they span
+            for ladder in &ladders {
+                let x = (ladder.left + ladder.right) / 2.0;
+                if !Self::touches_ladder(a, ladder, x) {
+                    continue;
+                }
+                for (to, b) in nodes.iter().enumerate() {
+                    if to != from && Self::touches_ladder(b, ladder, x) {
+                        links[from].push(NavLink {
+                            to,
+                            kind: NavLinkKind::Climb,
+                            departure_x: x,
+                            landing_x: x,
+                            rise: b.top - a.top,
+                            cost: Self::center_distance(a, b) + NAV_CLIMB_PENALTY,
+                        });
+                    }
+                }
+            }
+        }
+
+        Self { nodes, links }
+    }
+
+    // Platform an agent standing (or falling) at `position` belongs to
+    pub fn platform_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.nodes.iter()
+            .enumerate()
+            .filter(|(_, node)| x >= node.left && x <= node.right && node.top <= y + NAV_EDGE_MARGIN)
+            .max_by(|(_, a), (_, b)| a.top.total_cmp(&b.top))
+            .map(|(index, _)| index)
+    }
+
+    // First step of the cheapest route from one position to another, if they are on different platforms
+    pub fn next_step(&self, from: (f32, f32), to: (f32, f32), agent: &NavAgent) -> Option<NavStep> {
+        let start = self.platform_at(from.0, from.1)?;
+        let goal = self.platform_at(to.0, to.1)?;
+        if start == goal {
+            return None;
+        }
+        self.find_path(start, goal, agent)?.into_iter().next()
+    }
+
+    // A* over platforms, skipping links the agent cannot traverse
+    pub fn find_path(&self, start: usize, goal: usize, agent: &NavAgent) -> Option<Vec<NavStep>> {
+        let mut open = BinaryHeap::new();
+        let mut best_cost: HashMap<usize, f32> = HashMap::new();
+        let mut came_from: HashMap<usize, (usize, NavLink)> = HashMap::new();
+
+        best_cost.insert(start, 0.0);
+        open.push(OpenEntry { node: start, estimate: self.heuristic(start, goal) });
+
+        while let Some(OpenEntry { node, .. }) = open.pop() {
+            if node == goal {
+                return Some(self.reconstruct(&came_from, goal));
+            }
+
+            let cost_here = best_cost[&node];
+            for link in self.links[node].iter().filter(|link| agent.can_traverse(link)) {
+                let cost = cost_here + link.cost;
+                if best_cost.get(&link.to).is_none_or(|&known| cost < known) {
+                    best_cost.insert(link.to, cost);
+                    came_from.insert(link.to, (node, *link));
+                    open.push(OpenEntry { node: link.to, estimate: cost + self.heuristic(link.to, goal) });
+                }
+            }
+        }
+
+        None
+    }
+
+    fn reconstruct(&self, came_from: &HashMap<usize, (usize, NavLink)>, goal: usize) -> Vec<NavStep> {
+        let mut steps = Vec::new();
+        let mut node = goal;
+        while let Some(&(previous, link)) = came_from.get(&node) {
+            steps.push(NavStep {
+                platform_id: self.nodes[node].platform_id,
+                kind: link.kind,
+                departure_x: link.departure_x,
+                landing_x: link.landing_x,
+            });
+            node = previous;
+        }
+        steps.reverse();
+        steps
+    }
+
+    fn heuristic(&self, from: usize, to: usize) -> f32 {
+        Self::center_distance(&self.nodes[from], &self.nodes[to])
+    }
+
+    fn center_distance(a: &NavSurface, b: &NavSurface) -> f32 {
+        let dx = (a.left + a.right) / 2.0 - (b.left + b.right) / 2.0;
+        let dy = a.top - b.top;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    fn touches_ladder(platform: &NavSurface, ladder: &NavSurface, x: f32) -> bool {
+        x >= platform.left - NAV_EDGE_MARGIN
+            && x <= platform.right + NAV_EDGE_MARGIN
+            && platform.top >= ladder.bottom - NAV_EDGE_MARGIN
+            && platform.top <= ladder.top + NAV_EDGE_MARGIN
+    }
+
+    // Work out how (and whether) an agent could get from platform `a` onto platform `b`
+    fn platform_link(a: &NavSurface, b: &NavSurface, to: usize) -> Option<NavLink> {
+        let rise = b.top - a.top;
+        let margin = NAV_EDGE_MARGIN;
+
+        let (departure_x, landing_x) = if b.left >= a.right {
+            (a.right - margin, b.left + margin)
+        } else if b.right <= a.left {
+            (a.left + margin, b.right - margin)
+        } else if rise.abs() <= NAV_SAME_HEIGHT {
+            // Overlapping at the same height - effectively one surface
+            let x = a.left.max(b.left);
+            (x, x)
+        } else if rise > 0.0 {
+            // Platform above: jump up from beside it, not from underneath
+            if b.left - margin >= a.left {
+                (b.left - margin, b.left + margin)
+            } else if b.right + margin <= a.right {
+                (b.right + margin, b.right - margin)
+            } else {
+                return None;
+            }
+        } else {
+            // Platform below: walk off an edge it extends past
+            if b.right > a.right + margin {
+                (a.right - margin, a.right + margin)
+            } else if b.left < a.left - margin {
+                (a.left + margin, a.left - margin)
+            } else {
+                return None;
+            }
+        };
+
+        let gap = (landing_x - departure_x).abs() - 2.0 * margin;
+        let kind = if rise.abs() <= NAV_SAME_HEIGHT && gap <= NAV_WALK_GAP {
+            NavLinkKind::Walk
+        } else if rise < -NAV_SAME_HEIGHT && gap <= NAV_WALK_GAP {
+            NavLinkKind::Drop
+        } else {
+            NavLinkKind::Jump
+        };
+
+        let penalty = if kind == NavLinkKind::Jump { NAV_JUMP_PENALTY } else { 0.0 };
+        Some(NavLink {
+            to,
+            kind,
+            departure_x,
+            landing_x,
+            rise,
+            cost: Self::center_distance(a, b) + penalty,
+        })
+    }
+}
+
+// Min-heap entry for A*
+struct OpenEntry {
+    node: usize,
+    estimate: f32,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so BinaryHeap pops the lowest estimate first
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reaches about 1.65 units high and 2.2 units across on level ground
+    const AGENT: NavAgent = NavAgent { move_speed: 2.0, jump_force: 6.0, gravity: 9.81, can_climb: false };
+
+    fn platform(platform_id: u32, left: f32, right: f32, top: f32) -> NavSurface {
+        NavSurface { platform_id, left, right, top, bottom: top, is_ladder: false }
+    }
+
+    #[test]
+    fn jumps_onto_a_low_platform_above() {
+        let graph = NavGraph::build(&[platform(1, 0.0, 10.0, 0.0), platform(2, 4.0, 8.0, 1.0)]);
+
+        let path = graph.find_path(0, 1, &AGENT).expect("platform should be reachable");
+        assert_eq!(path.len(), 1);
+        assert_eq!(path[0].kind, NavLinkKind::Jump);
+        assert_eq!(path[0].platform_id, 2);
+        // Taken from beside the platform, not from underneath it
+        assert!(path[0].departure_x < 4.0 && path[0].landing_x > 4.0);
+    }
+
+    #[test]
+    fn gap_wider_than_a_jump_is_not_traversed() {
+        let graph = NavGraph::build(&[platform(1, 0.0, 10.0, 0.0), platform(2, 20.0, 30.0, 0.0)]);
+
+        let link = graph.links[0].iter().find(|link| link.to == 1).expect("a jump link is still recorded");
+        assert_eq!(link.kind, NavLinkKind::Jump);
+        assert!(!AGENT.can_traverse(link));
+        assert!(graph.find_path(0, 1, &AGENT).is_none());
+    }
+
+    #[test]
+    fn drop_is_one_way() {
+        let graph = NavGraph::build(&[platform(1, 0.0, 10.0, 3.0), platform(2, 0.0, 20.0, 0.0)]);
+
+        let down = graph.find_path(0, 1, &AGENT).expect("the lower platform should be reachable");
+        assert_eq!(down.len(), 1);
+        assert_eq!(down[0].kind, NavLinkKind::Drop);
+
+        // Three units is above the agent's jump height, so there is no way back up
+        assert!(graph.find_path(1, 0, &AGENT).is_none());
+    }
+
+    #[test]
+    fn unreachable_goal_returns_none() {
+        let surfaces = [
+            platform(1, 0.0, 10.0, 0.0),
+            platform(2, 4.0, 8.0, 1.0),
+            platform(3, 4.0, 8.0, 5.0),
+            NavSurface { platform_id: 4, left: 7.0, right: 7.5, top: 5.0, bottom: 0.0, is_ladder: true },
+        ];
+        let graph = NavGraph::build(&surfaces);
+
+        assert!(graph.find_path(0, 2, &AGENT).is_none());
+        assert!(graph.next_step((5.0, 0.5), (6.0, 5.5), &AGENT).is_none());
+
+        // The ladder only helps agents that can climb
+        let climber = NavAgent { can_climb: true, ..AGENT };
+        let path = graph.find_path(0, 2, &climber).expect("the ladder should reach the top platform");
+        assert!(path.iter().any(|step| step.kind == NavLinkKind::Climb));
+    }
+}
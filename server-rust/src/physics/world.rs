@@ -3,6 +3,8 @@ use rapier2d::prelude::*;
 // Import Vector2 explicitly from nalgebra re-export
 use rapier2d::na::Vector2;
 use std::collections::HashMap;
+use crate::config::*;
+use crate::physics::navigation::NavGraph;
 
 // Physics world management for server-authoritative simulation
 pub struct PhysicsWorld {
@@ -20,16 +22,29 @@ pub struct PhysicsWorld {
     // Mapping between SpacetimeDB entity IDs and Rapier2D handles
     pub entity_to_body: HashMap<u32, RigidBodyHandle>,
     pub body_to_entity: HashMap<RigidBodyHandle, u32>,
+    // Static map geometry, keyed by platform table ID
+    pub platform_to_body: HashMap<u32, RigidBodyHandle>,
+
+    // Navigation graphs built from platform geometry, keyed by map ID
+    pub nav_graphs: HashMap<u32, NavGraph>,
     
     pub gravity: Vector2<f32>,
     pub integration_parameters: IntegrationParameters,
 }
 
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PhysicsWorld {
     // See: https://rapier.rs/docs/user_guides/rust/getting_started for physics world creation
     pub fn new() -> Self {
-        let mut integration_parameters = IntegrationParameters::default();
-        integration_parameters.dt = crate::config::PHYSICS_TIMESTEP; // 50Hz simulation
+        let integration_parameters = IntegrationParameters {
+            dt: PHYSICS_TIMESTEP, // 50Hz simulation
+            ..IntegrationParameters::default()
+        };
         
         Self {
             rigid_body_set: RigidBodySet::new(),
@@ -45,17 +60,21 @@ impl PhysicsWorld {
             
             entity_to_body: HashMap::new(),
             body_to_entity: HashMap::new(),
+            platform_to_body: HashMap::new(),
+
+            nav_graphs: HashMap::new(),
             
-            gravity: Vector2::new(0.0, crate::config::GRAVITY), // Standard gravity
+            gravity: Vector2::new(0.0, GRAVITY), // Standard gravity
             integration_parameters,
         }
     }
 
-    // Create a dynamic rigid body for a player entity
+    // Create a dynamic rigid body for a character entity (players, monsters, NPCs)
     // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBodyBuilder.html
-    pub fn create_player_body(&mut self, entity_id: u32, position: Vector2<f32>, mass: f32) -> RigidBodyHandle {
+    pub fn create_character_body(&mut self, entity_id: u32, position: Vector2<f32>, mass: f32) -> RigidBodyHandle {
         let rigid_body = RigidBodyBuilder::dynamic()
             .translation(position)
+            .lock_rotations() // Characters stay upright
             .build();
         
         let body_handle = self.rigid_body_set.insert(rigid_body);
@@ -66,6 +85,8 @@ impl PhysicsWorld {
             .density(mass / 1.0) // Adjust density to achieve desired mass
             .friction(0.5)
             .restitution(0.0) // No bouncing for platformer feel
+            .collision_groups(Self::character_groups())
+            .solver_groups(Self::character_groups())
             .build();
             
         self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
@@ -77,9 +98,61 @@ impl PhysicsWorld {
         body_handle
     }
 
+    // Create a small dynamic body for a dropped item
+    // Drops fall onto terrain but pass through characters and each other.
+    pub fn create_item_drop_body(&mut self, entity_id: u32, position: Vector2<f32>, mass: f32) -> RigidBodyHandle {
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .translation(position)
+            .lock_rotations()
+            .build();
+
+        let body_handle = self.rigid_body_set.insert(rigid_body);
+
+        let groups = InteractionGroups::new(
+            Group::from_bits_truncate(COLLISION_GROUP_ITEM),
+            Group::from_bits_truncate(COLLISION_GROUP_TERRAIN),
+        );
+        let collider = ColliderBuilder::cuboid(ITEM_DROP_HALF_SIZE, ITEM_DROP_HALF_SIZE)
+            .mass(mass)
+            .friction(1.0)
+            .restitution(0.0)
+            .collision_groups(groups)
+            .solver_groups(groups)
+            .build();
+
+        self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
+
+        self.entity_to_body.insert(entity_id, body_handle);
+        self.body_to_entity.insert(body_handle, entity_id);
+
+        body_handle
+    }
+
+    // Create a fixed body for a prop entity; props block movement like terrain
+    pub fn create_prop_body(&mut self, entity_id: u32, position: Vector2<f32>, size: Vector2<f32>) -> RigidBodyHandle {
+        let rigid_body = RigidBodyBuilder::fixed()
+            .translation(position)
+            .build();
+
+        let body_handle = self.rigid_body_set.insert(rigid_body);
+
+        let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
+            .friction(0.7)
+            .collision_groups(Self::terrain_groups())
+            .solver_groups(Self::terrain_groups())
+            .build();
+
+        self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
+
+        self.entity_to_body.insert(entity_id, body_handle);
+        self.body_to_entity.insert(body_handle, entity_id);
+
+        body_handle
+    }
+
     // Create a static ground/platform collider
     // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBodyBuilder.html
-    pub fn create_static_ground(&mut self, position: Vector2<f32>, size: Vector2<f32>) {
+    pub fn create_static_ground(&mut self, platform_id: u32, position: Vector2<f32>, size: Vector2<f32>) {
         let rigid_body = RigidBodyBuilder::fixed()
             .translation(position)
             .build();
@@ -88,23 +161,127 @@ impl PhysicsWorld {
         
         let collider = ColliderBuilder::cuboid(size.x / 2.0, size.y / 2.0)
             .friction(0.7)
+            .collision_groups(Self::terrain_groups())
+            .solver_groups(Self::terrain_groups())
             .build();
             
         self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
+
+        // Replacing a platform removes its previous collider
+        if let Some(previous) = self.platform_to_body.insert(platform_id, body_handle) {
+            self.remove_rigid_body(previous);
+        }
     }
 
-    // Apply horizontal movement force to a player
+    // Remove a static platform collider created by create_static_ground
+    pub fn remove_static_ground(&mut self, platform_id: u32) {
+        if let Some(body_handle) = self.platform_to_body.remove(&platform_id) {
+            self.remove_rigid_body(body_handle);
+        }
+    }
+
+    // Create a small, fast projectile body with continuous collision detection
+    // CCD keeps arrows and bolts from tunneling through thin platforms between steps.
+    // Contacts are only solved against terrain; characters are reported as collision events.
+    // See: https://rapier.rs/docs/user_guides/rust/rigid_body_ccd
+    pub fn create_projectile_body(
+        &mut self,
+        entity_id: u32,
+        position: Vector2<f32>,
+        velocity: Vector2<f32>,
+        radius: f32,
+        gravity_scale: f32,
+    ) -> RigidBodyHandle {
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .translation(position)
+            .linvel(velocity)
+            .gravity_scale(gravity_scale)
+            .lock_rotations()
+            .ccd_enabled(true)
+            .build();
+
+        let body_handle = self.rigid_body_set.insert(rigid_body);
+
+        let collider = ColliderBuilder::ball(radius)
+            .restitution(0.0)
+            .collision_groups(InteractionGroups::new(
+                Group::from_bits_truncate(COLLISION_GROUP_PROJECTILE),
+                Group::from_bits_truncate(COLLISION_GROUP_TERRAIN | COLLISION_GROUP_CHARACTER),
+            ))
+            .solver_groups(InteractionGroups::new(
+                Group::from_bits_truncate(COLLISION_GROUP_PROJECTILE),
+                Group::from_bits_truncate(COLLISION_GROUP_TERRAIN),
+            ))
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .build();
+
+        self.collider_set.insert_with_parent(collider, body_handle, &mut self.rigid_body_set);
+
+        self.entity_to_body.insert(entity_id, body_handle);
+        self.body_to_entity.insert(body_handle, entity_id);
+
+        body_handle
+    }
+
+    // Remove an entity's rigid body and all attached colliders
+    // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBodySet.html#method.remove
+    pub fn remove_body(&mut self, entity_id: u32) {
+        if let Some(body_handle) = self.entity_to_body.remove(&entity_id) {
+            self.body_to_entity.remove(&body_handle);
+            self.remove_rigid_body(body_handle);
+        }
+    }
+
+    fn remove_rigid_body(&mut self, body_handle: RigidBodyHandle) {
+        self.rigid_body_set.remove(
+            body_handle,
+            &mut self.island_manager,
+            &mut self.collider_set,
+            &mut self.impulse_joint_set,
+            &mut self.multibody_joint_set,
+            true,
+        );
+    }
+
+    // Resolve the entity owning a collider; terrain colliders have no entity
+    pub fn collider_entity(&self, collider_handle: ColliderHandle) -> Option<u32> {
+        let body_handle = self.collider_set.get(collider_handle)?.parent()?;
+        self.body_to_entity.get(&body_handle).copied()
+    }
+
+    fn terrain_groups() -> InteractionGroups {
+        InteractionGroups::new(Group::from_bits_truncate(COLLISION_GROUP_TERRAIN), Group::ALL)
+    }
+
+    fn character_groups() -> InteractionGroups {
+        InteractionGroups::new(Group::from_bits_truncate(COLLISION_GROUP_CHARACTER), Group::ALL)
+    }
+
+    // Apply horizontal movement force to a character
+    // The impulse steers horizontal velocity toward input * move_speed, so holding
+    // a direction reaches move_speed instead of accelerating without bound.
     // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBody.html#method.apply_impulse
     pub fn apply_movement_force(&mut self, entity_id: u32, horizontal_input: f32, move_speed: f32) {
         if let Some(&body_handle) = self.entity_to_body.get(&entity_id) {
             if let Some(body) = self.rigid_body_set.get_mut(body_handle) {
                 // Apply horizontal force based on input
-                let force = Vector2::new(horizontal_input * move_speed * body.mass(), 0.0);
+                let target_velocity = horizontal_input * move_speed;
+                let force = Vector2::new((target_velocity - body.linvel().x) * body.mass(), 0.0);
                 body.apply_impulse(force, true);
             }
         }
     }
 
+    // Check for terrain just ahead of an entity's feet in the given horizontal direction
+    // Used by AI to turn around at platform edges instead of walking off.
+    pub fn has_ground_ahead(&self, entity_id: u32, direction: f32, lookahead: f32) -> bool {
+        let Some((position, _)) = self.get_body_state(entity_id) else {
+            return false;
+        };
+        let probe = Vector2::new(position.x + direction.signum() * lookahead, position.y);
+        self.find_ground_below(probe, CHARACTER_HALF_HEIGHT + EDGE_PROBE_DEPTH).is_some()
+    }
+
     // Apply jump impulse if on ground
     // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBody.html#method.apply_impulse
     pub fn apply_jump_force(&mut self, entity_id: u32, jump_force: f32) -> bool {
@@ -123,6 +300,49 @@ impl PhysicsWorld {
         false
     }
 
+    // Throw a character back after a hit by overriding its velocity
+    // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBody.html#method.set_linvel
+    pub fn apply_knockback(&mut self, entity_id: u32, velocity: Vector2<f32>) {
+        if let Some(&body_handle) = self.entity_to_body.get(&entity_id) {
+            if let Some(body) = self.rigid_body_set.get_mut(body_handle) {
+                body.set_linvel(velocity, true);
+            }
+        }
+    }
+
+    // Launch a character horizontally, keeping its vertical velocity (dash skills)
+    pub fn set_horizontal_velocity(&mut self, entity_id: u32, velocity_x: f32) {
+        if let Some(&body_handle) = self.entity_to_body.get(&entity_id) {
+            if let Some(body) = self.rigid_body_set.get_mut(body_handle) {
+                let velocity = Vector2::new(velocity_x, body.linvel().y);
+                body.set_linvel(velocity, true);
+            }
+        }
+    }
+
+    // Move a character up to `distance` horizontally, stopping short of any wall in the way
+    // Returns the new position. Signed distance: negative moves left.
+    // See: https://docs.rs/rapier2d/latest/rapier2d/dynamics/struct.RigidBody.html#method.set_translation
+    pub fn teleport_horizontal(&mut self, entity_id: u32, distance: f32) -> Option<Vector2<f32>> {
+        let body_handle = *self.entity_to_body.get(&entity_id)?;
+        let position = *self.rigid_body_set.get(body_handle)?.translation();
+
+        let ray = Ray::new(point![position.x, position.y], Vector2::new(distance.signum(), 0.0));
+        let filter = QueryFilter::new().groups(InteractionGroups::new(
+            Group::ALL,
+            Group::from_bits_truncate(COLLISION_GROUP_TERRAIN),
+        ));
+        let travel = self.query_pipeline
+            .cast_ray(&self.rigid_body_set, &self.collider_set, &ray, distance.abs(), true, filter)
+            .map_or(distance.abs(), |(_, hit_distance)| (hit_distance - CHARACTER_RADIUS).max(0.0));
+
+        let destination = Vector2::new(position.x + distance.signum() * travel, position.y);
+        let body = self.rigid_body_set.get_mut(body_handle)?;
+        body.set_translation(destination, true);
+        body.set_linvel(Vector2::zeros(), true);
+        Some(destination)
+    }
+
     // Simplified ground detection using velocity
     // TODO: Implement proper raycasting for more accurate ground detection
     fn is_on_ground(&self, body_handle: RigidBodyHandle) -> bool {
@@ -134,9 +354,70 @@ impl PhysicsWorld {
         }
     }
 
-    // Step the physics simulation
+    // Cast a ray straight down and return the first terrain surface point within `max_distance`
+    // Returns None when there is no platform below, or when `position` is already inside terrain.
+    // See: https://docs.rs/rapier2d/latest/rapier2d/pipeline/struct.QueryPipeline.html#method.cast_ray
+    pub fn find_ground_below(&self, position: Vector2<f32>, max_distance: f32) -> Option<Vector2<f32>> {
+        let ray = Ray::new(point![position.x, position.y], Vector2::new(0.0, -1.0));
+        let filter = QueryFilter::new().groups(InteractionGroups::new(
+            Group::ALL,
+            Group::from_bits_truncate(COLLISION_GROUP_TERRAIN),
+        ));
+
+        let (_, distance) = self.query_pipeline.cast_ray(
+            &self.rigid_body_set,
+            &self.collider_set,
+            &ray,
+            max_distance,
+            true,
+            filter,
+        )?;
+
+        if distance <= 0.0 {
+            return None;
+        }
+        Some(Vector2::new(position.x, position.y - distance))
+    }
+
+    // Entities whose character colliders overlap an axis-aligned box, excluding `exclude_entity_id`
+    // Reflects the world as of the last step; bodies created since then are not found yet.
+    // See: https://docs.rs/rapier2d/latest/rapier2d/pipeline/struct.QueryPipeline.html#method.intersections_with_shape
+    pub fn characters_in_box(&self, center: Vector2<f32>, half_extents: Vector2<f32>, exclude_entity_id: u32) -> Vec<u32> {
+        let shape = Cuboid::new(half_extents);
+        let shape_pos = Isometry::translation(center.x, center.y);
+        let mut filter = QueryFilter::new().groups(InteractionGroups::new(
+            Group::ALL,
+            Group::from_bits_truncate(COLLISION_GROUP_CHARACTER),
+        ));
+        if let Some(&body_handle) = self.entity_to_body.get(&exclude_entity_id) {
+            filter = filter.exclude_rigid_body(body_handle);
+        }
+
+        let mut entities = Vec::new();
+        self.query_pipeline.intersections_with_shape(
+            &self.rigid_body_set,
+            &self.collider_set,
+            &shape_pos,
+            &shape,
+            filter,
+            |collider_handle| {
+                if let Some(entity_id) = self.collider_entity(collider_handle) {
+                    entities.push(entity_id);
+                }
+                true // Keep searching
+            },
+        );
+        entities
+    }
+
+    // Step the physics simulation and return the collision events raised during the step
     // See: https://docs.rs/rapier2d/latest/rapier2d/pipeline/struct.PhysicsPipeline.html#method.step
-    pub fn step(&mut self) {
+    pub fn step(&mut self) -> Vec<CollisionEvent> {
+        // See: https://docs.rs/rapier2d/latest/rapier2d/pipeline/struct.ChannelEventCollector.html
+        let (collision_send, collision_recv) = rapier2d::crossbeam::channel::unbounded();
+        let (contact_force_send, _contact_force_recv) = rapier2d::crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(collision_send, contact_force_send);
+
         self.physics_pipeline.step(
             &self.gravity,
             &self.integration_parameters,
@@ -150,8 +431,10 @@ impl PhysicsWorld {
             &mut self.ccd_solver,
             Some(&mut self.query_pipeline),
             &(),
-            &(),
+            &event_handler,
         );
+
+        collision_recv.try_iter().collect()
     }
 
     // Get updated position and velocity for database sync
@@ -0,0 +1,259 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{CharacterStats, CombatStats, StatBonus, StatKind};
+use crate::config::*;
+use crate::utils::GameMath;
+use crate::reducers::equipment_reducers::equipment_bonus;
+use crate::reducers::party_reducers::party_members_on_map;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::job::job;
+use crate::tables::combat::{vitals, combat_stats};
+use crate::tables::entity::entity;
+use crate::tables::monster::{monster, monster_template};
+use crate::tables::movement_controller::movement_controller;
+use crate::tables::player::{player, player_entity};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Spend unallocated ability points on a stat
+#[reducer]
+pub fn allocate_ap(ctx: &ReducerContext, stat: StatKind, amount: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let mut character = ctx.db.character_stats().player_id().find(player.player_id)
+        .ok_or("Character not found")?;
+
+    if amount == 0 {
+        return Err("Amount must be positive".into());
+    }
+    if amount > character.ap {
+        return Err(format!("Not enough AP: have {}, need {}", character.ap, amount));
+    }
+
+    let value = stat_mut(&mut character, stat);
+    *value = value.checked_add(amount)
+        .filter(|new_value| *new_value <= MAX_STAT)
+        .ok_or(format!("{:?} cannot exceed {}", stat, MAX_STAT))?;
+    character.ap -= amount;
+
+    refresh_character_stats(ctx, &character);
+    ctx.db.character_stats().player_id().update(character);
+
+    Ok(())
+}
+
+// Fetch a player's character record, creating a fresh level 1 character on first use
+pub fn ensure_character(ctx: &ReducerContext, player_id: u32) -> CharacterStats {
+    if let Some(character) = ctx.db.character_stats().player_id().find(player_id) {
+        return character;
+    }
+
+    ctx.db.character_stats().insert(CharacterStats {
+        player_id,
+        level: 1,
+        job_id: BEGINNER_JOB_ID,
+        exp: 0,
+        max_hp: PLAYER_BASE_HP,
+        max_mp: PLAYER_BASE_MP,
+        str: STARTING_STAT,
+        dex: STARTING_STAT,
+        int: STARTING_STAT,
+        luk: STARTING_STAT,
+        ap: STARTING_AP,
+        sp: 0,
+        mesos: 0,
+        speed: BASE_SPEED_PERCENT,
+        jump: BASE_JUMP_PERCENT,
+    })
+}
+
+// Add EXP to a character and apply any level ups it earns
+pub fn grant_exp(ctx: &ReducerContext, player_id: u32, amount: u64) {
+    let Some(mut character) = ctx.db.character_stats().player_id().find(player_id) else {
+        return;
+    };
+    if character.level >= MAX_LEVEL {
+        return;
+    }
+
+    character.exp = character.exp.saturating_add(amount);
+
+    // Max HP/MP growth depends on the character's job
+    let (hp_per_level, mp_per_level) = ctx.db.job().job_id().find(character.job_id)
+        .map_or((HP_PER_LEVEL, MP_PER_LEVEL), |job| (job.hp_per_level, job.mp_per_level));
+
+    let starting_level = character.level;
+    while character.level < MAX_LEVEL && character.exp >= GameMath::exp_to_next_level(character.level) {
+        character.exp -= GameMath::exp_to_next_level(character.level);
+        character.level += 1;
+        character.ap += AP_PER_LEVEL;
+        character.sp += SP_PER_LEVEL;
+        character.max_hp += hp_per_level;
+        character.max_mp += mp_per_level;
+    }
+    if character.level >= MAX_LEVEL {
+        character.exp = 0;
+    }
+
+    if character.level > starting_level {
+        log::info!("Player {} reached level {}", player_id, character.level);
+        refresh_character_stats(ctx, &character);
+        restore_vitals(ctx, player_id);
+    }
+    ctx.db.character_stats().player_id().update(character);
+}
+
+// Give the EXP of a killed monster to the killer, shared with party members on the same map
+// Must run before the monster is despawned.
+pub fn award_kill_exp(ctx: &ReducerContext, monster_entity_id: u32, killer_entity_id: u32) {
+    let Some(killer) = ctx.db.player_entity().entity_id().find(killer_entity_id) else {
+        return;
+    };
+    let Some(template) = ctx.db.monster().entity_id().find(monster_entity_id)
+        .and_then(|monster| ctx.db.monster_template().template_id().find(monster.template_id)) else {
+        return;
+    };
+
+    let Some(monster_entity) = ctx.db.entity().entity_id().find(monster_entity_id) else {
+        return;
+    };
+
+    let sharing: Vec<(u32, u32)> = party_members_on_map(ctx, killer.player_id, monster_entity.map_id).into_iter()
+        .filter_map(|player_id| ctx.db.character_stats().player_id().find(player_id)
+            .map(|character| (player_id, character.level)))
+        .collect();
+    let level_sum: u32 = sharing.iter().map(|(_, level)| level).sum();
+    for &(player_id, level) in &sharing {
+        grant_exp(ctx, player_id, GameMath::party_exp_share(template.exp as u64, level, level_sum, sharing.len()));
+    }
+}
+
+// Credit mesos to a character, failing rather than exceeding MAX_MESOS
+pub fn add_mesos(ctx: &ReducerContext, player_id: u32, amount: u64) -> Result<(), String> {
+    let mut character = ctx.db.character_stats().player_id().find(player_id)
+        .ok_or("Character not found")?;
+    character.mesos = character.mesos.checked_add(amount)
+        .filter(|mesos| *mesos <= MAX_MESOS)
+        .ok_or(format!("Cannot hold more than {} mesos", MAX_MESOS))?;
+    ctx.db.character_stats().player_id().update(character);
+    Ok(())
+}
+
+// Debit mesos from a character, failing if the balance is too low
+pub fn spend_mesos(ctx: &ReducerContext, player_id: u32, amount: u64) -> Result<(), String> {
+    let mut character = ctx.db.character_stats().player_id().find(player_id)
+        .ok_or("Character not found")?;
+    character.mesos = character.mesos.checked_sub(amount)
+        .ok_or(format!("Not enough mesos: have {}, need {}", character.mesos, amount))?;
+    ctx.db.character_stats().player_id().update(character);
+    Ok(())
+}
+
+// Push stats derived from the character record onto the player's live entity
+// Call whenever anything that feeds derived stats changes.
+pub fn refresh_character_stats(ctx: &ReducerContext, character: &CharacterStats) {
+    let owned: Vec<u32> = ctx.db.player_entity().player_id().filter(character.player_id)
+        .map(|owned| owned.entity_id)
+        .collect();
+
+    for entity_id in owned {
+        if ctx.db.combat_stats().entity_id().find(entity_id).is_some() {
+            ctx.db.combat_stats().entity_id().update(derive_combat_stats(ctx, entity_id, character));
+        }
+
+        if let Some(mut vitals) = ctx.db.vitals().entity_id().find(entity_id) {
+            (vitals.max_hp, vitals.max_mp) = derive_max_vitals(ctx, character);
+            vitals.hp = vitals.hp.min(vitals.max_hp);
+            vitals.mp = vitals.mp.min(vitals.max_mp);
+            ctx.db.vitals().entity_id().update(vitals);
+        }
+
+        if let Some(mut controller) = ctx.db.movement_controller().entity_id().find(entity_id) {
+            (controller.move_speed, controller.jump_force) = derive_movement(ctx, character);
+            ctx.db.movement_controller().entity_id().update(controller);
+        }
+    }
+}
+
+// Combat numbers for a character's entity, including worn equipment
+// Attack scales with the job's primary stat and, less so, its secondary stat.
+pub fn derive_combat_stats(ctx: &ReducerContext, entity_id: u32, character: &CharacterStats) -> CombatStats {
+    let (primary, secondary) = ctx.db.job().job_id().find(character.job_id)
+        .map_or((StatKind::Str, StatKind::Dex), |job| (job.primary_stat, job.secondary_stat));
+    let bonus = equipment_bonus(ctx, character.player_id);
+    let dex = total_stat(character, &bonus, StatKind::Dex);
+    let luk = total_stat(character, &bonus, StatKind::Luk);
+
+    CombatStats {
+        entity_id,
+        attack: PLAYER_BASE_ATTACK + bonus.attack
+            + total_stat(character, &bonus, primary)
+            + total_stat(character, &bonus, secondary) / 4,
+        defense: PLAYER_BASE_DEFENSE + bonus.defense,
+        accuracy: PLAYER_BASE_ACCURACY + bonus.accuracy + dex * 4 / 5 + luk / 2,
+        avoidability: PLAYER_BASE_AVOIDABILITY + bonus.avoidability + luk / 2 + dex / 4,
+        critical_rate: PLAYER_BASE_CRITICAL_RATE,
+        critical_damage: BASE_CRITICAL_DAMAGE,
+    }
+}
+
+// Max HP and MP for a character, including worn equipment: (max_hp, max_mp)
+pub fn derive_max_vitals(ctx: &ReducerContext, character: &CharacterStats) -> (u32, u32) {
+    let bonus = equipment_bonus(ctx, character.player_id);
+    (character.max_hp + bonus.max_hp, character.max_mp + bonus.max_mp)
+}
+
+// Movement speed and jump force for a character, including worn equipment: (move_speed, jump_force)
+pub fn derive_movement(ctx: &ReducerContext, character: &CharacterStats) -> (f32, f32) {
+    let bonus = equipment_bonus(ctx, character.player_id);
+    (
+        PLAYER_MOVE_SPEED * (character.speed + bonus.speed) as f32 / 100.0,
+        PLAYER_JUMP_FORCE * (character.jump + bonus.jump) as f32 / 100.0,
+    )
+}
+
+pub fn stat_value(character: &CharacterStats, stat: StatKind) -> u32 {
+    match stat {
+        StatKind::Str => character.str,
+        StatKind::Dex => character.dex,
+        StatKind::Int => character.int,
+        StatKind::Luk => character.luk,
+    }
+}
+
+// A stat with equipment bonuses added
+fn total_stat(character: &CharacterStats, bonus: &StatBonus, stat: StatKind) -> u32 {
+    stat_value(character, stat) + match stat {
+        StatKind::Str => bonus.str,
+        StatKind::Dex => bonus.dex,
+        StatKind::Int => bonus.int,
+        StatKind::Luk => bonus.luk,
+    }
+}
+
+fn stat_mut(character: &mut CharacterStats, stat: StatKind) -> &mut u32 {
+    match stat {
+        StatKind::Str => &mut character.str,
+        StatKind::Dex => &mut character.dex,
+        StatKind::Int => &mut character.int,
+        StatKind::Luk => &mut character.luk,
+    }
+}
+
+// Refill HP and MP on every living entity the player controls
+fn restore_vitals(ctx: &ReducerContext, player_id: u32) {
+    let owned: Vec<u32> = ctx.db.player_entity().player_id().filter(player_id)
+        .map(|owned| owned.entity_id)
+        .collect();
+
+    for entity_id in owned {
+        if let Some(mut vitals) = ctx.db.vitals().entity_id().find(entity_id) {
+            if vitals.hp == 0 {
+                continue;
+            }
+            vitals.hp = vitals.max_hp;
+            vitals.mp = vitals.max_mp;
+            ctx.db.vitals().entity_id().update(vitals);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{ChatChannel, ChatCleanupTimer, ChatMessage, ChatMute, Player, RateLimitKind, SuspicionReason};
+use crate::config::*;
+use crate::utils::{BlocklistFilter, WordFilter};
+use crate::reducers::guild_reducers::guild_of;
+use crate::reducers::party_reducers::party_of;
+use crate::reducers::moderation_reducers::{record_violation, require_admin, require_scheduler, try_consume_rate_limit};
+// Import table access traits
+use crate::tables::chat::{chat_message, chat_mute};
+use crate::tables::entity::entity;
+use crate::tables::player::{player, player_entity};
+use crate::tables::timers::chat_cleanup_timer;
+
+static BLOCKLIST: BlocklistFilter = BlocklistFilter::new(CHAT_BLOCKED_WORDS);
+
+// The filter applied to every chat message and guild notice; swap the implementation here
+pub fn word_filter() -> &'static dyn WordFilter {
+    &BLOCKLIST
+}
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Schedule chat retention cleanup
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_chat(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.chat_cleanup_timer().try_insert(ChatCleanupTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(CHAT_CLEANUP_INTERVAL_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Send a message to the caller's map, party, guild or the whole world
+#[reducer]
+pub fn send_chat(ctx: &ReducerContext, channel: ChatChannel, text: String) -> Result<(), String> {
+    let sender = chat_sender(ctx, channel)?;
+    let text = prepare_text(&text)?;
+
+    let mut message = new_message(ctx, channel, &sender, text);
+    match channel {
+        ChatChannel::Map => {
+            message.map_id = ctx.db.player_entity().player_id().filter(sender.player_id)
+                .find_map(|owned| ctx.db.entity().entity_id().find(owned.entity_id))
+                .map(|entity| entity.map_id)
+                .ok_or("You must be in the world to use map chat")?;
+        }
+        ChatChannel::Whisper => return Err("Use whisper to message a player".into()),
+        ChatChannel::Party => {
+            message.party_id = party_of(ctx, sender.player_id)
+                .ok_or("You are not in a party")?;
+        }
+        ChatChannel::Guild => {
+            message.guild_id = guild_of(ctx, sender.player_id)
+                .ok_or("You are not in a guild")?;
+        }
+        ChatChannel::World => {}
+    }
+    ctx.db.chat_message().insert(message);
+
+    Ok(())
+}
+
+// Send a private message to an online player by character name
+#[reducer]
+pub fn whisper(ctx: &ReducerContext, recipient_name: String, text: String) -> Result<(), String> {
+    let sender = chat_sender(ctx, ChatChannel::Whisper)?;
+    let text = prepare_text(&text)?;
+
+    let recipient = ctx.db.player().iter()
+        .find(|player| !player.name.is_empty() && player.name.eq_ignore_ascii_case(recipient_name.trim()))
+        .ok_or(format!("{} is not online", recipient_name.trim()))?;
+    if recipient.player_id == sender.player_id {
+        return Err("Cannot whisper to yourself".into());
+    }
+
+    let mut message = new_message(ctx, ChatChannel::Whisper, &sender, text);
+    message.recipient_player_id = recipient.player_id;
+    ctx.db.chat_message().insert(message);
+
+    Ok(())
+}
+
+// Bar a player from chatting for `duration_ms` (admin only)
+#[reducer]
+pub fn mute_player(ctx: &ReducerContext, player_id: u32, duration_ms: u64, reason: String) -> Result<(), String> {
+    require_admin(ctx)?;
+
+    let mute = ChatMute {
+        player_id,
+        muted_until: ctx.timestamp + Duration::from_millis(duration_ms),
+        reason,
+        muted_by: ctx.sender,
+    };
+    if ctx.db.chat_mute().player_id().find(player_id).is_some() {
+        ctx.db.chat_mute().player_id().update(mute);
+    } else {
+        ctx.db.chat_mute().insert(mute);
+    }
+
+    Ok(())
+}
+
+// Lift a player's mute (admin only)
+#[reducer]
+pub fn unmute_player(ctx: &ReducerContext, player_id: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+
+    if !ctx.db.chat_mute().player_id().delete(player_id) {
+        return Err("That player is not muted".into());
+    }
+
+    Ok(())
+}
+
+// Remove chat messages and mutes that are past their time
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn cleanup_chat_messages(ctx: &ReducerContext, _timer: ChatCleanupTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let retention = Duration::from_millis(CHAT_RETENTION_MS);
+    let expired: Vec<u64> = ctx.db.chat_message().iter()
+        .filter(|message| ctx.timestamp.duration_since(message.sent_at).is_some_and(|age| age >= retention))
+        .map(|message| message.message_id)
+        .collect();
+    for message_id in expired {
+        ctx.db.chat_message().message_id().delete(message_id);
+    }
+
+    let lifted: Vec<u32> = ctx.db.chat_mute().iter()
+        .filter(|mute| mute.muted_until <= ctx.timestamp)
+        .map(|mute| mute.player_id)
+        .collect();
+    for player_id in lifted {
+        ctx.db.chat_mute().player_id().delete(player_id);
+    }
+
+    Ok(())
+}
+
+// The calling player, if they are allowed to send on `channel` right now
+fn chat_sender(ctx: &ReducerContext, channel: ChatChannel) -> Result<Player, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    if let Some(mute) = ctx.db.chat_mute().player_id().find(player.player_id) {
+        if mute.muted_until > ctx.timestamp {
+            return Err(format!("You are muted: {}", mute.reason));
+        }
+    }
+
+    let kind = if channel == ChatChannel::World { RateLimitKind::Megaphone } else { RateLimitKind::Chat };
+    if !try_consume_rate_limit(ctx, kind) {
+        record_violation(ctx, SuspicionReason::ChatRateLimit);
+        return Err("You are sending messages too quickly".into());
+    }
+
+    Ok(player)
+}
+
+// Trim, strip control characters, enforce the length limit and apply the word filter
+fn prepare_text(text: &str) -> Result<String, String> {
+    let cleaned: String = text.trim().chars().filter(|c| !c.is_control()).collect();
+    if cleaned.is_empty() {
+        return Err("Message is empty".into());
+    }
+    if cleaned.chars().count() > CHAT_MAX_LENGTH {
+        return Err(format!("Messages are limited to {} characters", CHAT_MAX_LENGTH));
+    }
+    Ok(word_filter().filter(&cleaned))
+}
+
+fn new_message(ctx: &ReducerContext, channel: ChatChannel, sender: &Player, text: String) -> ChatMessage {
+    ChatMessage {
+        message_id: 0, // Auto-incremented
+        channel,
+        sender_player_id: sender.player_id,
+        sender_name: sender.name.clone(),
+        map_id: 0,
+        recipient_player_id: 0,
+        party_id: 0,
+        guild_id: 0,
+        text,
+        sent_at: ctx.timestamp,
+    }
+}
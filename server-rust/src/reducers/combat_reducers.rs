@@ -0,0 +1,388 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{AttackCooldown, AttackEvent, CombatStats, CombatEventCleanupTimer, DamageEvent, DamageOutcome, Entity, EntityType};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::utils::RandomExtensions;
+use crate::reducers::entity_reducers::despawn_entity;
+use crate::reducers::monster_ai_reducers::provoke_monster;
+use crate::reducers::physics_reducers::with_physics_world;
+use crate::reducers::character_reducers::award_kill_exp;
+use crate::reducers::loot_reducers::drop_loot;
+use crate::reducers::monster_reducers::start_respawn_interval;
+use crate::reducers::moderation_reducers::require_scheduler;
+use crate::reducers::status_effect_reducers::{attack_multiplier, is_incapacitated};
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::combat::{vitals, combat_stats, attack_cooldown, attack_event, damage_event};
+use crate::tables::movement_controller::movement_controller;
+use crate::tables::player::{player, player_entity};
+use crate::tables::monster::monster;
+use crate::tables::timers::combat_event_cleanup_timer;
+
+// One attack landing on one target, before any stats are applied
+#[derive(Clone, Copy, Debug)]
+pub struct Hit {
+    pub attacker_entity_id: u32,
+    pub target_entity_id: u32,
+    pub source_position: DbVector2, // Knockback pushes the target away from this point
+    pub bonus_attack: u32,          // Added to the attacker's attack, e.g. a projectile's payload
+    pub multiplier: f32,            // Skill damage multiplier, 1.0 for a basic hit
+}
+
+// Melee hitbox placed in front of an attacker
+#[derive(Clone, Copy, Debug)]
+pub struct MeleeArea {
+    pub size: DbVector2,        // Width and height of the hitbox
+    pub offset: f32,            // Distance from the attacker to the hitbox center along its facing
+    pub max_targets: usize,     // Nearest targets are hit first
+    pub hit_count: u32,         // Damage lines dealt to each target
+}
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Schedule cleanup of old combat events
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_combat(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.combat_event_cleanup_timer().try_insert(CombatEventCleanupTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(COMBAT_EVENT_RETENTION_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Run a hit through the damage pipeline: evade roll, damage roll, critical roll, defense,
+// then knockback, i-frames and death for hits that land.
+// Returns None when the target cannot be hit right now (not hostile, no HP, dead or invulnerable).
+pub fn apply_damage(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, hit: &Hit) -> Option<DamageEvent> {
+    let attacker = ctx.db.entity().entity_id().find(hit.attacker_entity_id)?;
+    let target = ctx.db.entity().entity_id().find(hit.target_entity_id)?;
+    if !is_hostile(attacker.entity_type, target.entity_type) {
+        return None;
+    }
+
+    let mut vitals = ctx.db.vitals().entity_id().find(target.entity_id)?;
+    if vitals.hp == 0 || ctx.timestamp < vitals.invulnerable_until {
+        return None;
+    }
+
+    let attacker_stats = combat_stats_or_default(ctx, attacker.entity_id);
+    let target_stats = combat_stats_or_default(ctx, target.entity_id);
+    let (outcome, amount) = roll_damage(ctx, &attacker_stats, &target_stats, hit, attack_multiplier(ctx, attacker.entity_id));
+
+    vitals.hp = vitals.hp.saturating_sub(amount);
+    let killed = vitals.hp == 0;
+
+    let event = ctx.db.damage_event().insert(DamageEvent {
+        event_id: 0, // Auto-incremented
+        map_id: target.map_id,
+        attacker_entity_id: attacker.entity_id,
+        target_entity_id: target.entity_id,
+        outcome,
+        amount,
+        killed,
+        created_at: ctx.timestamp,
+    });
+
+    // Even a miss makes a monster notice its attacker
+    provoke_monster(ctx, target.entity_id, attacker.entity_id);
+
+    if outcome != DamageOutcome::Miss {
+        // Only players get i-frames; monsters take every hit of a multi-hit attack
+        if target.entity_type == EntityType::Player {
+            vitals.invulnerable_until = ctx.timestamp + Duration::from_millis(PLAYER_INVULNERABILITY_MS);
+        }
+        if !killed {
+            let direction = if target.position.x >= hit.source_position.x { 1.0 } else { -1.0 };
+            let velocity = DbVector2::new(direction * KNOCKBACK_SPEED_X, KNOCKBACK_SPEED_Y);
+            physics_world.apply_knockback(target.entity_id, velocity.to_nalgebra());
+        }
+    }
+    ctx.db.vitals().entity_id().update(vitals);
+
+    if killed {
+        handle_death(ctx, physics_world, &target, attacker.entity_id);
+    }
+
+    Some(event)
+}
+
+// Basic attack in the direction the attacker is facing
+// The client only asks to swing; direction comes from the server-side movement controller,
+// which turns with horizontal input, and what gets hit is resolved against the physics world.
+#[reducer]
+pub fn attack(ctx: &ReducerContext) -> Result<(), String> {
+    let (_, attacker_id) = living_player_entity(ctx)?;
+    let facing = start_action(ctx, attacker_id, BASIC_ATTACK_COOLDOWN_MS)?;
+
+    let (width, height) = BASIC_ATTACK_HITBOX_SIZE;
+    let area = MeleeArea {
+        size: DbVector2::new(width, height),
+        offset: BASIC_ATTACK_HITBOX_OFFSET,
+        max_targets: BASIC_ATTACK_MAX_TARGETS,
+        hit_count: 1,
+    };
+    with_physics_world(|physics_world| melee_strike(ctx, physics_world, attacker_id, facing, &area, 1.0, None))
+        .ok_or("Physics world not initialized")?
+        .ok_or("Attacker has no entity")?;
+
+    Ok(())
+}
+
+// The caller's player id and the living entity they control
+pub fn living_player_entity(ctx: &ReducerContext) -> Result<(u32, u32), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let entity_id = ctx.db.player_entity().player_id().filter(player.player_id)
+        .map(|owned| owned.entity_id)
+        .next()
+        .ok_or("No spawned entity")?;
+    if is_dead(ctx, entity_id) {
+        return Err("Dead players cannot act".into());
+    }
+    Ok((player.player_id, entity_id))
+}
+
+// Begin an attack or skill: fails while a previous one is still locking the entity,
+// otherwise locks it for `lock_ms` and returns the direction it faces (-1.0 left, 1.0 right)
+pub fn start_action(ctx: &ReducerContext, entity_id: u32, lock_ms: u64) -> Result<f32, String> {
+    if is_incapacitated(ctx, entity_id) {
+        return Err("Cannot act while stunned or frozen".into());
+    }
+
+    let cooldown = ctx.db.attack_cooldown().entity_id().find(entity_id);
+    if cooldown.as_ref().is_some_and(|cooldown| ctx.timestamp < cooldown.ready_at) {
+        return Err("Still recovering from the previous action".into());
+    }
+    let ready_at = ctx.timestamp + Duration::from_millis(lock_ms);
+    match cooldown {
+        Some(mut cooldown) => {
+            cooldown.ready_at = ready_at;
+            ctx.db.attack_cooldown().entity_id().update(cooldown);
+        }
+        None => {
+            ctx.db.attack_cooldown().insert(AttackCooldown { entity_id, ready_at });
+        }
+    }
+
+    let facing = ctx.db.movement_controller().entity_id().find(entity_id)
+        .map_or(1.0, |controller| controller.facing);
+
+    Ok(facing)
+}
+
+// Swing a melee hitbox for an attacker and run every target it catches through the damage pipeline
+// Records an attack_event and returns it, or None if the attacker no longer exists.
+pub fn melee_strike(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    attacker_entity_id: u32,
+    facing: f32,
+    area: &MeleeArea,
+    multiplier: f32,
+    skill_id: Option<u32>,
+) -> Option<AttackEvent> {
+    let attacker = ctx.db.entity().entity_id().find(attacker_entity_id)?;
+
+    // Use the live body position; the entity row is only synced once per step
+    let position = physics_world.get_body_state(attacker_entity_id)
+        .map_or(attacker.position, |(position, _)| DbVector2::from_nalgebra(position));
+    let hitbox_center = DbVector2::new(position.x + facing * area.offset, position.y);
+
+    let mut candidates: Vec<Entity> = physics_world
+        .characters_in_box(hitbox_center.to_nalgebra(), (area.size * 0.5).to_nalgebra(), attacker_entity_id)
+        .into_iter()
+        .filter_map(|entity_id| ctx.db.entity().entity_id().find(entity_id))
+        .collect();
+    candidates.sort_by(|a, b| {
+        (a.position.x - position.x).abs().total_cmp(&(b.position.x - position.x).abs())
+    });
+
+    let mut hit_entity_ids = Vec::new();
+    for target in candidates {
+        if hit_entity_ids.len() >= area.max_targets {
+            break;
+        }
+        // Later lines stop once the target dies; only targets that took damage count as hit
+        let hit = Hit {
+            attacker_entity_id,
+            target_entity_id: target.entity_id,
+            source_position: position,
+            bonus_attack: 0,
+            multiplier,
+        };
+        let damaged = (0..area.hit_count)
+            .map_while(|_| apply_damage(ctx, physics_world, &hit))
+            .filter(|event| event.outcome != DamageOutcome::Miss)
+            .count();
+        if damaged > 0 {
+            hit_entity_ids.push(target.entity_id);
+        }
+    }
+
+    Some(record_attack_event(ctx, &attacker, facing, skill_id, (hitbox_center, area.size), hit_entity_ids))
+}
+
+// Publish an attack so clients can animate it; `hitbox` is (center, size)
+pub fn record_attack_event(
+    ctx: &ReducerContext,
+    attacker: &Entity,
+    facing: f32,
+    skill_id: Option<u32>,
+    (hitbox_center, hitbox_size): (DbVector2, DbVector2),
+    hit_entity_ids: Vec<u32>,
+) -> AttackEvent {
+    ctx.db.attack_event().insert(AttackEvent {
+        event_id: 0, // Auto-incremented
+        map_id: attacker.map_id,
+        attacker_entity_id: attacker.entity_id,
+        skill_id,
+        facing,
+        hitbox_center,
+        hitbox_size,
+        hit_entity_ids,
+        created_at: ctx.timestamp,
+    })
+}
+
+// Whether an entity has HP and has run out of it
+pub fn is_dead(ctx: &ReducerContext, entity_id: u32) -> bool {
+    ctx.db.vitals().entity_id().find(entity_id).is_some_and(|vitals| vitals.hp == 0)
+}
+
+// Monsters hurt players they touch, using the monster's own attack
+// Called from physics_step after positions have been synced.
+pub fn process_contact_damage(ctx: &ReducerContext, physics_world: &mut PhysicsWorld) {
+    let monster_ids: Vec<u32> = ctx.db.monster().iter().map(|monster| monster.entity_id).collect();
+
+    for monster_id in monster_ids {
+        let Some(entity) = ctx.db.entity().entity_id().find(monster_id) else {
+            continue;
+        };
+
+        let (reach_x, reach_y) = MONSTER_TOUCH_RANGE;
+        let touching: Vec<u32> = ctx.db.entity().map_cell().filter(entity.map_id)
+            .filter(|candidate| candidate.entity_type == EntityType::Player)
+            .filter(|candidate| {
+                (candidate.position.x - entity.position.x).abs() <= reach_x
+                    && (candidate.position.y - entity.position.y).abs() <= reach_y
+            })
+            .map(|candidate| candidate.entity_id)
+            .collect();
+
+        for target_entity_id in touching {
+            apply_damage(ctx, physics_world, &Hit {
+                attacker_entity_id: entity.entity_id,
+                target_entity_id,
+                source_position: entity.position,
+                bonus_attack: 0,
+                multiplier: 1.0,
+            });
+        }
+    }
+}
+
+// Remove attack and damage events older than the retention window
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn cleanup_combat_events(ctx: &ReducerContext, _timer: CombatEventCleanupTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let retention = Duration::from_millis(COMBAT_EVENT_RETENTION_MS);
+    let is_expired = |created_at| ctx.timestamp.duration_since(created_at).is_some_and(|age| age >= retention);
+
+    let expired_attacks: Vec<u64> = ctx.db.attack_event().iter()
+        .filter(|event| is_expired(event.created_at))
+        .map(|event| event.event_id)
+        .collect();
+    for event_id in expired_attacks {
+        ctx.db.attack_event().event_id().delete(event_id);
+    }
+
+    let expired_damage: Vec<u64> = ctx.db.damage_event().iter()
+        .filter(|event| is_expired(event.created_at))
+        .map(|event| event.event_id)
+        .collect();
+    for event_id in expired_damage {
+        ctx.db.damage_event().event_id().delete(event_id);
+    }
+
+    Ok(())
+}
+
+// Players fight monsters; nobody else takes damage from anyone
+fn is_hostile(attacker: EntityType, target: EntityType) -> bool {
+    matches!(
+        (attacker, target),
+        (EntityType::Player, EntityType::Monster) | (EntityType::Monster, EntityType::Player)
+    )
+}
+
+// Entities without a stats row fight with nothing but their base hit
+fn combat_stats_or_default(ctx: &ReducerContext, entity_id: u32) -> CombatStats {
+    ctx.db.combat_stats().entity_id().find(entity_id).unwrap_or(CombatStats {
+        entity_id,
+        attack: 0,
+        defense: 0,
+        accuracy: 0,
+        avoidability: 0,
+        critical_rate: 0.0,
+        critical_damage: 1.0,
+    })
+}
+
+// Roll the outcome and amount of a hit
+fn roll_damage(
+    ctx: &ReducerContext,
+    attacker: &CombatStats,
+    target: &CombatStats,
+    hit: &Hit,
+    attack_multiplier: f32,
+) -> (DamageOutcome, u32) {
+    let mut rng = ctx.rng();
+
+    // Evade chance grows with the target's avoidability relative to the attacker's accuracy
+    let total = (attacker.accuracy + target.avoidability).max(1) as f32;
+    let evade_chance = target.avoidability as f32 / total * MAX_EVADE_CHANCE;
+    if rng.chance(evade_chance) {
+        return (DamageOutcome::Miss, 0);
+    }
+
+    let attack = (attacker.attack + hit.bonus_attack) as f32 * attack_multiplier * hit.multiplier;
+    let mut damage = attack * rng.range_f32(DAMAGE_MASTERY, 1.0);
+
+    let critical = rng.chance(attacker.critical_rate);
+    if critical {
+        damage *= attacker.critical_damage;
+    }
+
+    // A landed hit always does at least 1 damage
+    damage -= target.defense as f32 * DEFENSE_REDUCTION;
+    let amount = (damage.round() as u32).max(1);
+
+    let outcome = if critical { DamageOutcome::Critical } else { DamageOutcome::Hit };
+    (outcome, amount)
+}
+
+// A target's HP just reached zero
+fn handle_death(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, target: &Entity, killer_entity_id: u32) {
+    match target.entity_type {
+        // Dead players stay where they fell until they call respawn
+        EntityType::Player => {
+            log::info!("Player entity {} was killed by entity {}", target.entity_id, killer_entity_id);
+        }
+        EntityType::Monster => {
+            log::info!("Monster entity {} was killed by entity {}", target.entity_id, killer_entity_id);
+            award_kill_exp(ctx, target.entity_id, killer_entity_id);
+            drop_loot(ctx, physics_world, target, killer_entity_id);
+            start_respawn_interval(ctx, target.entity_id);
+            despawn_entity(ctx, physics_world, target.entity_id);
+        }
+        _ => {
+            log::info!("Entity {} was killed by entity {}", target.entity_id, killer_entity_id);
+            despawn_entity(ctx, physics_world, target.entity_id);
+        }
+    }
+}
@@ -1,23 +1,25 @@
 use spacetimedb::{reducer, ReducerContext, Table};
 use crate::tables::{Player, LoggedOutPlayer};
 // Import table access traits
-use crate::tables::player::{player, logged_out_player};
-use crate::tables::movement_controller::movement_controller;
-use crate::tables::entity::entity;
+use crate::tables::player::{player, logged_out_player, player_entity};
+use crate::reducers::entity_reducers::despawn_entity_outside_step;
+use crate::reducers::trade_reducers::cancel_trades_of;
+use crate::reducers::friend_reducers::refresh_presence;
 
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
 
 #[reducer(client_connected)]
 pub fn connect(ctx: &ReducerContext) {
     // Check if player was previously logged out
-    if let Some(player) = ctx.db.logged_out_player().identity().find(&ctx.sender) {
+    if let Some(player) = ctx.db.logged_out_player().identity().find(ctx.sender) {
         // Move from logged_out_player to player table
         ctx.db.player().insert(Player {
             identity: player.identity,
             player_id: player.player_id,
             name: player.name,
         });
-        ctx.db.logged_out_player().identity().delete(&player.identity);
+        ctx.db.logged_out_player().identity().delete(player.identity);
+        refresh_presence(ctx, player.player_id);
     } else {
         // Create new player
         ctx.db.player().insert(Player {
@@ -30,15 +32,17 @@ pub fn connect(ctx: &ReducerContext) {
 
 #[reducer(client_disconnected)]
 pub fn disconnect(ctx: &ReducerContext) {
-    let player = ctx.db.player().identity().find(&ctx.sender)
+    let player = ctx.db.player().identity().find(ctx.sender)
         .expect("Player not found");
 
-    // Remove any player entities from the arena
-    for controller in ctx.db.movement_controller().player_id().filter(&player.player_id) {
-        if let Some(entity) = ctx.db.entity().entity_id().find(&controller.entity_id) {
-            ctx.db.entity().entity_id().delete(&entity.entity_id);
-            ctx.db.movement_controller().entity_id().delete(&entity.entity_id);
-        }
+    cancel_trades_of(ctx, player.player_id, "Trader disconnected");
+
+    // Remove any player entities from the world
+    let owned: Vec<u32> = ctx.db.player_entity().player_id().filter(player.player_id)
+        .map(|owned| owned.entity_id)
+        .collect();
+    for entity_id in owned {
+        despawn_entity_outside_step(ctx, entity_id);
     }
 
     // Move player to logged_out_player table
@@ -47,5 +51,6 @@ pub fn disconnect(ctx: &ReducerContext) {
         player_id: player.player_id,
         name: player.name,
     });
-    ctx.db.player().identity().delete(&player.identity);
+    ctx.db.player().identity().delete(player.identity);
+    refresh_presence(ctx, player.player_id);
 }
\ No newline at end of file
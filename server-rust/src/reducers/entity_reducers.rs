@@ -0,0 +1,215 @@
+use spacetimedb::{ReducerContext, Table};
+use std::time::Duration;
+use crate::tables::{Entity, EntityType, MovementController, Vitals, CombatStats, Monster, MonsterAi, MonsterAiState, MonsterTemplate, Npc, ItemDrop, Prop};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::reducers::physics_reducers::{create_entity_physics_body, with_physics_world};
+use crate::utils::GameMath;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::physics_body::physics_body;
+use crate::tables::movement_controller::movement_controller;
+use crate::tables::player::player_entity;
+use crate::tables::player_input::{player_input, input_command};
+use crate::tables::monster::{monster, monster_ai};
+use crate::tables::npc::npc;
+use crate::tables::item_drop::item_drop;
+use crate::tables::projectile::projectile;
+use crate::tables::prop::prop;
+use crate::tables::combat::{vitals, combat_stats, attack_cooldown};
+use crate::tables::status_effect::status_effect;
+
+// Spawn and despawn helpers shared by every entity type.
+// They take the physics world directly so they work both inside and outside the physics step;
+// outside of it, wrap spawns in `with_physics_world` and despawn with `despawn_entity_outside_step`.
+
+// Insert the base entity row every entity type shares
+pub fn insert_entity(
+    ctx: &ReducerContext,
+    entity_type: EntityType,
+    map_id: u32,
+    position: DbVector2,
+    velocity: DbVector2,
+    mass: u32,
+) -> Entity {
+    let (cell_x, cell_y) = GameMath::position_to_cell(position);
+
+    ctx.db.entity().insert(Entity {
+        entity_id: 0, // Auto-incremented
+        position,
+        velocity,
+        mass,
+        entity_type,
+        map_id,
+        cell_x,
+        cell_y,
+    })
+}
+
+// HP/MP component, starting full
+pub fn insert_vitals(ctx: &ReducerContext, entity_id: u32, max_hp: u32, max_mp: u32) -> Vitals {
+    ctx.db.vitals().insert(Vitals {
+        entity_id,
+        hp: max_hp,
+        max_hp,
+        mp: max_mp,
+        max_mp,
+        invulnerable_until: ctx.timestamp,
+    })
+}
+
+// Platformer movement component for anything that walks and jumps
+pub fn insert_movement_controller(ctx: &ReducerContext, entity_id: u32, move_speed: f32, jump_force: f32) -> MovementController {
+    ctx.db.movement_controller().insert(MovementController {
+        entity_id,
+        move_speed,
+        jump_force,
+        can_jump: false, // Will be updated by ground detection
+        facing: 1.0,
+        last_input_sequence: 0,
+        last_input_tick: 0,
+    })
+}
+
+pub fn spawn_monster(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    template: &MonsterTemplate,
+    spawn_id: u32,
+    map_id: u32,
+    position: DbVector2,
+) -> Entity {
+    let entity = insert_entity(ctx, EntityType::Monster, map_id, position, DbVector2::zero(), MONSTER_MASS);
+
+    ctx.db.monster().insert(Monster {
+        entity_id: entity.entity_id,
+        template_id: template.template_id,
+        spawn_id,
+    });
+    insert_movement_controller(ctx, entity.entity_id, template.move_speed, template.jump_force);
+    ctx.db.monster_ai().insert(MonsterAi {
+        entity_id: entity.entity_id,
+        state: MonsterAiState::Idle,
+        home: position,
+        target_entity_id: None,
+        state_until: ctx.timestamp,
+    });
+    insert_vitals(ctx, entity.entity_id, template.max_hp, template.max_mp);
+    ctx.db.combat_stats().insert(CombatStats {
+        entity_id: entity.entity_id,
+        attack: template.attack,
+        defense: template.defense,
+        accuracy: template.accuracy,
+        avoidability: template.avoidability,
+        critical_rate: 0.0,
+        critical_damage: BASE_CRITICAL_DAMAGE,
+    });
+    create_entity_physics_body(ctx, physics_world, &entity);
+
+    entity
+}
+
+pub fn spawn_npc(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    npc_id: u32,
+    name: String,
+    map_id: u32,
+    position: DbVector2,
+) -> Entity {
+    let entity = insert_entity(ctx, EntityType::Npc, map_id, position, DbVector2::zero(), NPC_MASS);
+
+    ctx.db.npc().insert(Npc {
+        entity_id: entity.entity_id,
+        npc_id,
+        name,
+    });
+    create_entity_physics_body(ctx, physics_world, &entity);
+
+    entity
+}
+
+pub fn spawn_item_drop(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    item_id: u32,
+    quantity: u32,
+    map_id: u32,
+    position: DbVector2,
+    owner_player_id: Option<u32>,
+) -> Entity {
+    let entity = insert_entity(ctx, EntityType::ItemDrop, map_id, position, DbVector2::zero(), ITEM_DROP_MASS);
+
+    ctx.db.item_drop().insert(ItemDrop {
+        entity_id: entity.entity_id,
+        item_id,
+        quantity,
+        owner_player_id,
+        exclusive_until: ctx.timestamp + Duration::from_millis(DROP_OWNERSHIP_MS),
+        expires_at: ctx.timestamp + Duration::from_millis(DROP_LIFETIME_MS),
+    });
+    create_entity_physics_body(ctx, physics_world, &entity);
+
+    entity
+}
+
+pub fn spawn_prop(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    prop_id: u32,
+    size: DbVector2,
+    map_id: u32,
+    position: DbVector2,
+) -> Entity {
+    // Props are fixed bodies, so mass is irrelevant
+    let entity = insert_entity(ctx, EntityType::Prop, map_id, position, DbVector2::zero(), 0);
+
+    ctx.db.prop().insert(Prop {
+        entity_id: entity.entity_id,
+        prop_id,
+        size,
+    });
+    create_entity_physics_body(ctx, physics_world, &entity);
+
+    entity
+}
+
+// Remove an entity of any type together with its components and physics body
+pub fn despawn_entity(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, entity_id: u32) {
+    physics_world.remove_body(entity_id);
+    delete_entity_rows(ctx, entity_id);
+}
+
+// Despawn from outside the physics step
+// The rows are deleted even when the physics world is not initialized, so nothing is leaked.
+pub fn despawn_entity_outside_step(ctx: &ReducerContext, entity_id: u32) {
+    with_physics_world(|physics_world| physics_world.remove_body(entity_id));
+    delete_entity_rows(ctx, entity_id);
+}
+
+fn delete_entity_rows(ctx: &ReducerContext, entity_id: u32) {
+    ctx.db.physics_body().entity_id().delete(entity_id);
+    ctx.db.movement_controller().entity_id().delete(entity_id);
+    ctx.db.vitals().entity_id().delete(entity_id);
+    ctx.db.combat_stats().entity_id().delete(entity_id);
+    ctx.db.attack_cooldown().entity_id().delete(entity_id);
+    ctx.db.status_effect().entity_id().delete(entity_id);
+
+    // Player ownership and pending input
+    if let Some(owner) = ctx.db.player_entity().entity_id().find(entity_id) {
+        ctx.db.input_command().player_id().delete(owner.player_id);
+        ctx.db.player_entity().entity_id().delete(entity_id);
+    }
+    ctx.db.player_input().entity_id().delete(entity_id);
+
+    // Type-specific components
+    ctx.db.monster().entity_id().delete(entity_id);
+    ctx.db.monster_ai().entity_id().delete(entity_id);
+    ctx.db.npc().entity_id().delete(entity_id);
+    ctx.db.item_drop().entity_id().delete(entity_id);
+    ctx.db.projectile().entity_id().delete(entity_id);
+    ctx.db.prop().entity_id().delete(entity_id);
+
+    ctx.db.entity().entity_id().delete(entity_id);
+}
@@ -0,0 +1,231 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{CharacterStats, EquipSlot, EquipTemplate, Equipment, InventoryItem, InventoryTab, ItemTemplate, StatBonus, StatKind};
+use crate::reducers::character_reducers::{refresh_character_stats, stat_value};
+use crate::reducers::inventory_reducers::{first_free_slot, item_at};
+use crate::reducers::job_reducers::job_lineage;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::equipment::{equip_template, equipment};
+use crate::tables::item::{item_template, inventory_item};
+use crate::tables::player::player;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed starter equips, registering each as an Equip tab item as well
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_equipment(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    // Defaults shared by every equip; each definition below overrides what it needs
+    let base = |item_id: u32, slot: EquipSlot| EquipTemplate {
+        item_id,
+        slot,
+        required_level: 0,
+        required_job_id: None,
+        required_str: 0,
+        required_dex: 0,
+        required_int: 0,
+        required_luk: 0,
+        bonus: StatBonus::default(),
+    };
+
+    // (name, sell price, equip)
+    let equips = [
+        ("Sword", 50, EquipTemplate {
+            bonus: StatBonus { attack: 17, ..Default::default() },
+            ..base(1302000, EquipSlot::Weapon)
+        }),
+        ("Wooden Staff", 50, EquipTemplate {
+            required_level: 8,
+            required_job_id: Some(200),
+            bonus: StatBonus { attack: 10, int: 2, ..Default::default() },
+            ..base(1382000, EquipSlot::Weapon)
+        }),
+        ("War Bow", 600, EquipTemplate {
+            required_level: 10,
+            required_job_id: Some(300),
+            required_dex: 25,
+            bonus: StatBonus { attack: 30, ..Default::default() },
+            ..base(1452002, EquipSlot::Weapon)
+        }),
+        ("Garnier", 500, EquipTemplate {
+            required_level: 10,
+            required_job_id: Some(400),
+            required_luk: 25,
+            bonus: StatBonus { attack: 15, ..Default::default() },
+            ..base(1472000, EquipSlot::Weapon)
+        }),
+        ("Brown Skullcap", 100, EquipTemplate {
+            required_level: 5,
+            bonus: StatBonus { defense: 5, ..Default::default() },
+            ..base(1002008, EquipSlot::Hat)
+        }),
+        ("White Undershirt", 20, EquipTemplate {
+            bonus: StatBonus { defense: 3, ..Default::default() },
+            ..base(1040002, EquipSlot::Top)
+        }),
+        ("Blue Jean Shorts", 20, EquipTemplate {
+            bonus: StatBonus { defense: 2, ..Default::default() },
+            ..base(1060002, EquipSlot::Bottom)
+        }),
+        ("Red Rubber Boots", 60, EquipTemplate {
+            required_level: 5,
+            bonus: StatBonus { defense: 2, speed: 5, ..Default::default() },
+            ..base(1072001, EquipSlot::Shoes)
+        }),
+        ("Work Gloves", 80, EquipTemplate {
+            required_level: 10,
+            bonus: StatBonus { defense: 2, accuracy: 2, ..Default::default() },
+            ..base(1082002, EquipSlot::Gloves)
+        }),
+        ("Green Adventurer Cape", 300, EquipTemplate {
+            required_level: 15,
+            bonus: StatBonus { defense: 3, dex: 1, avoidability: 2, ..Default::default() },
+            ..base(1102000, EquipSlot::Cape)
+        }),
+        ("Emerald Earrings", 250, EquipTemplate {
+            required_level: 15,
+            bonus: StatBonus { max_mp: 10, luk: 1, ..Default::default() },
+            ..base(1032000, EquipSlot::Accessory)
+        }),
+    ];
+
+    for (name, price, equip) in equips {
+        ctx.db.item_template().try_insert(ItemTemplate {
+            item_id: equip.item_id,
+            name: name.into(),
+            tab: InventoryTab::Equip,
+            max_stack: 1,
+            price,
+            rechargeable: false,
+        })?;
+        ctx.db.equip_template().try_insert(equip)?;
+    }
+
+    Ok(())
+}
+
+// Wear the equip in an Equip tab slot
+// Whatever was already worn in that body slot takes the equip's place in the inventory.
+#[reducer]
+pub fn equip_item(ctx: &ReducerContext, inventory_slot: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let character = ctx.db.character_stats().player_id().find(player.player_id)
+        .ok_or("Character not found")?;
+
+    let item = item_at(ctx, player.player_id, InventoryTab::Equip, inventory_slot)
+        .ok_or("No item in that slot")?;
+    let template = ctx.db.equip_template().item_id().find(item.item_id)
+        .ok_or("That item cannot be equipped")?;
+    check_requirements(ctx, &character, &template)?;
+
+    ctx.db.inventory_item().inventory_item_id().delete(item.inventory_item_id);
+
+    match worn_in(ctx, player.player_id, template.slot) {
+        Some(mut worn) => {
+            ctx.db.inventory_item().insert(InventoryItem {
+                inventory_item_id: 0, // Auto-incremented
+                player_id: player.player_id,
+                tab: InventoryTab::Equip,
+                slot: inventory_slot,
+                item_id: worn.item_id,
+                quantity: 1,
+            });
+            worn.item_id = item.item_id;
+            ctx.db.equipment().equipment_id().update(worn);
+        }
+        None => {
+            ctx.db.equipment().insert(Equipment {
+                equipment_id: 0, // Auto-incremented
+                player_id: player.player_id,
+                slot: template.slot,
+                item_id: item.item_id,
+            });
+        }
+    }
+
+    refresh_character_stats(ctx, &character);
+
+    Ok(())
+}
+
+// Take off the equip worn in a body slot and put it in the first free Equip tab slot
+#[reducer]
+pub fn unequip_item(ctx: &ReducerContext, slot: EquipSlot) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let character = ctx.db.character_stats().player_id().find(player.player_id)
+        .ok_or("Character not found")?;
+
+    let worn = worn_in(ctx, player.player_id, slot)
+        .ok_or(format!("Nothing is equipped in the {:?} slot", slot))?;
+    let inventory_slot = first_free_slot(ctx, player.player_id, InventoryTab::Equip)
+        .ok_or("Equip inventory is full")?;
+
+    ctx.db.inventory_item().insert(InventoryItem {
+        inventory_item_id: 0, // Auto-incremented
+        player_id: player.player_id,
+        tab: InventoryTab::Equip,
+        slot: inventory_slot,
+        item_id: worn.item_id,
+        quantity: 1,
+    });
+    ctx.db.equipment().equipment_id().delete(worn.equipment_id);
+
+    refresh_character_stats(ctx, &character);
+
+    Ok(())
+}
+
+// Sum of the bonuses from everything a character is wearing
+pub fn equipment_bonus(ctx: &ReducerContext, player_id: u32) -> StatBonus {
+    ctx.db.equipment().player_id().filter(player_id)
+        .filter_map(|worn| ctx.db.equip_template().item_id().find(worn.item_id))
+        .fold(StatBonus::default(), |total, equip| {
+            let bonus = equip.bonus;
+            StatBonus {
+                str: total.str + bonus.str,
+                dex: total.dex + bonus.dex,
+                int: total.int + bonus.int,
+                luk: total.luk + bonus.luk,
+                max_hp: total.max_hp + bonus.max_hp,
+                max_mp: total.max_mp + bonus.max_mp,
+                attack: total.attack + bonus.attack,
+                defense: total.defense + bonus.defense,
+                accuracy: total.accuracy + bonus.accuracy,
+                avoidability: total.avoidability + bonus.avoidability,
+                speed: total.speed + bonus.speed,
+                jump: total.jump + bonus.jump,
+            }
+        })
+}
+
+fn worn_in(ctx: &ReducerContext, player_id: u32, slot: EquipSlot) -> Option<Equipment> {
+    ctx.db.equipment().player_id().filter(player_id)
+        .find(|worn| worn.slot == slot)
+}
+
+// Level, job and base stat requirements; bonuses from other equips do not count
+fn check_requirements(ctx: &ReducerContext, character: &CharacterStats, equip: &EquipTemplate) -> Result<(), String> {
+    if character.level < equip.required_level {
+        return Err(format!("Requires level {}", equip.required_level));
+    }
+    if let Some(job_id) = equip.required_job_id {
+        if !job_lineage(ctx, character.job_id).contains(&job_id) {
+            return Err("Your job cannot wear this item".into());
+        }
+    }
+
+    let requirements = [
+        (StatKind::Str, equip.required_str),
+        (StatKind::Dex, equip.required_dex),
+        (StatKind::Int, equip.required_int),
+        (StatKind::Luk, equip.required_luk),
+    ];
+    for (stat, required) in requirements {
+        if stat_value(character, stat) < required {
+            return Err(format!("Requires {:?} {}", stat, required));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{Friend, FriendRequest, RateLimitKind, SuspicionReason};
+use crate::config::*;
+use crate::reducers::moderation_reducers::{record_failed_attempt, record_violation, try_consume_rate_limit};
+use crate::reducers::party_reducers::spawned_entity;
+// Import table access traits
+use crate::tables::friend::{friend, friend_request};
+use crate::tables::player::{player, logged_out_player};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Ask a character, online or not, to be friends
+// If they already asked the caller, the two become friends straight away.
+#[reducer]
+pub fn send_friend_request(ctx: &ReducerContext, name: String) -> Result<(), String> {
+    let requester = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    if !try_consume_rate_limit(ctx, RateLimitKind::FriendRequest) {
+        record_violation(ctx, SuspicionReason::FriendRequestRateLimit);
+        return Err("You are sending friend requests too quickly".into());
+    }
+
+    // Failed requests still cost their token, so names cannot be probed faster than the limit
+    request_friendship(ctx, requester.player_id, name.trim())
+        .inspect_err(|_| record_failed_attempt(ctx, RateLimitKind::FriendRequest))
+}
+
+// Accept a request addressed to the caller, adding each player to the other's list
+#[reducer]
+pub fn accept_friend_request(ctx: &ReducerContext, request_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let request = own_request(ctx, request_id, player.player_id)?;
+
+    befriend(ctx, request)
+}
+
+// Turn down a request addressed to the caller
+#[reducer]
+pub fn decline_friend_request(ctx: &ReducerContext, request_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    own_request(ctx, request_id, player.player_id)?;
+
+    ctx.db.friend_request().request_id().delete(request_id);
+
+    Ok(())
+}
+
+// Withdraw a request the caller sent
+#[reducer]
+pub fn cancel_friend_request(ctx: &ReducerContext, request_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    ctx.db.friend_request().request_id().find(request_id)
+        .filter(|request| request.requester_player_id == player.player_id)
+        .ok_or("Friend request not found")?;
+
+    ctx.db.friend_request().request_id().delete(request_id);
+
+    Ok(())
+}
+
+// End a friendship; both players lose each other from their lists
+#[reducer]
+pub fn remove_friend(ctx: &ReducerContext, friend_player_id: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    if !are_friends(ctx, player.player_id, friend_player_id) {
+        return Err("That player is not on your friend list".into());
+    }
+
+    for (owner, other) in [(player.player_id, friend_player_id), (friend_player_id, player.player_id)] {
+        let rows: Vec<u64> = ctx.db.friend().player_id().filter(owner)
+            .filter(|row| row.friend_player_id == other)
+            .map(|row| row.friendship_id)
+            .collect();
+        for friendship_id in rows {
+            ctx.db.friend().friendship_id().delete(friendship_id);
+        }
+    }
+
+    Ok(())
+}
+
+// Push a player's presence to everyone who lists them
+// Must be called whenever a player's name, online status or map changes: connect and disconnect
+// in connection_reducers.rs, and spawn_player_at in player_reducers.rs.
+pub fn refresh_presence(ctx: &ReducerContext, player_id: u32) {
+    let rows: Vec<Friend> = ctx.db.friend().friend_player_id().filter(player_id).collect();
+    for row in rows {
+        update_row(ctx, row);
+    }
+}
+
+fn are_friends(ctx: &ReducerContext, player_id: u32, other_player_id: u32) -> bool {
+    ctx.db.friend().player_id().filter(player_id)
+        .any(|row| row.friend_player_id == other_player_id)
+}
+
+// Outgoing requests hold a place on the list until they are answered
+fn check_capacity(ctx: &ReducerContext, player_id: u32, message: &str) -> Result<(), String> {
+    let friends = ctx.db.friend().player_id().filter(player_id).count();
+    let pending = ctx.db.friend_request().requester_player_id().filter(player_id).count();
+    if friends + pending >= MAX_FRIENDS {
+        return Err(format!("{} ({} friends)", message, MAX_FRIENDS));
+    }
+    Ok(())
+}
+
+fn own_request(ctx: &ReducerContext, request_id: u64, player_id: u32) -> Result<FriendRequest, String> {
+    ctx.db.friend_request().request_id().find(request_id)
+        .filter(|request| request.target_player_id == player_id)
+        .ok_or("Friend request not found".into())
+}
+
+// Resolve a character by name and ask them to be friends, or befriend them if they already asked
+fn request_friendship(ctx: &ReducerContext, requester_player_id: u32, name: &str) -> Result<(), String> {
+    let target_player_id = ctx.db.player().iter()
+        .find(|player| !player.name.is_empty() && player.name.eq_ignore_ascii_case(name))
+        .map(|player| player.player_id)
+        .or_else(|| ctx.db.logged_out_player().iter()
+            .find(|player| !player.name.is_empty() && player.name.eq_ignore_ascii_case(name))
+            .map(|player| player.player_id))
+        .ok_or(format!("No character named {}", name))?;
+
+    if target_player_id == requester_player_id {
+        return Err("Cannot add yourself as a friend".into());
+    }
+    if are_friends(ctx, requester_player_id, target_player_id) {
+        return Err(format!("{} is already your friend", name));
+    }
+
+    let reverse = ctx.db.friend_request().requester_player_id().filter(target_player_id)
+        .find(|request| request.target_player_id == requester_player_id);
+    if let Some(request) = reverse {
+        return befriend(ctx, request);
+    }
+
+    if ctx.db.friend_request().requester_player_id().filter(requester_player_id)
+        .any(|request| request.target_player_id == target_player_id) {
+        return Err("You have already sent that request".into());
+    }
+    check_capacity(ctx, requester_player_id, "Your friend list is full")?;
+    if ctx.db.friend_request().target_player_id().filter(target_player_id).count() >= MAX_INCOMING_FRIEND_REQUESTS {
+        return Err(format!("{} has too many pending friend requests", name));
+    }
+
+    ctx.db.friend_request().insert(FriendRequest {
+        request_id: 0, // Auto-incremented
+        requester_player_id,
+        target_player_id,
+        created_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// Turn a request into a friendship in both directions
+fn befriend(ctx: &ReducerContext, request: FriendRequest) -> Result<(), String> {
+    // Deleted first so the requester's place held by this request is freed
+    ctx.db.friend_request().request_id().delete(request.request_id);
+    check_capacity(ctx, request.target_player_id, "Your friend list is full")?;
+    check_capacity(ctx, request.requester_player_id, "Their friend list is full")?;
+
+    let pairs = [
+        (request.requester_player_id, request.target_player_id),
+        (request.target_player_id, request.requester_player_id),
+    ];
+    for (player_id, friend_player_id) in pairs {
+        let (name, online, map_id) = presence(ctx, friend_player_id);
+        ctx.db.friend().insert(Friend {
+            friendship_id: 0, // Auto-incremented
+            player_id,
+            friend_player_id,
+            name,
+            online,
+            map_id,
+            since: ctx.timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// Write a friend row back only when something its owner can see has changed
+fn update_row(ctx: &ReducerContext, mut row: Friend) {
+    let (name, online, map_id) = presence(ctx, row.friend_player_id);
+    if name != row.name || online != row.online || map_id != row.map_id {
+        row.name = name;
+        row.online = online;
+        row.map_id = map_id;
+        ctx.db.friend().friendship_id().update(row);
+    }
+}
+
+// Name, online status and current map of a player
+// Online means the identity is in `player`; offline characters are found in `logged_out_player`.
+fn presence(ctx: &ReducerContext, player_id: u32) -> (String, bool, u32) {
+    match ctx.db.player().player_id().find(player_id) {
+        Some(player) => {
+            let map_id = spawned_entity(ctx, player_id).map_or(0, |entity| entity.map_id);
+            (player.name, true, map_id)
+        }
+        None => {
+            let name = ctx.db.logged_out_player().player_id().find(player_id)
+                .map(|player| player.name)
+                .unwrap_or_default();
+            (name, false, 0)
+        }
+    }
+}
@@ -2,6 +2,21 @@ use spacetimedb::{reducer, ReducerContext, Table};
 use crate::tables::Config;
 use crate::config::*;
 use crate::reducers::physics_reducers::init_physics;
+use crate::reducers::moderation_reducers::init_moderation;
+use crate::reducers::monster_reducers::init_monsters;
+use crate::reducers::map_reducers::init_maps;
+use crate::reducers::combat_reducers::init_combat;
+use crate::reducers::job_reducers::init_jobs;
+use crate::reducers::skill_reducers::init_skills;
+use crate::reducers::status_effect_reducers::init_status_effects;
+use crate::reducers::inventory_reducers::init_items;
+use crate::reducers::equipment_reducers::init_equipment;
+use crate::reducers::loot_reducers::init_loot;
+use crate::reducers::shop_reducers::init_shops;
+use crate::reducers::trade_reducers::init_trades;
+use crate::reducers::chat_reducers::init_chat;
+use crate::reducers::party_reducers::init_parties;
+use crate::reducers::guild_reducers::init_guilds;
 use crate::tables::config::config;
 
 #[reducer(init)]
@@ -12,7 +27,22 @@ pub fn init(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
         id: 0,
         world_size: DEFAULT_WORLD_SIZE,
     });
+    init_maps(ctx)?;
     init_physics(ctx)?;
+    init_moderation(ctx)?;
+    init_monsters(ctx)?;
+    init_combat(ctx)?;
+    init_jobs(ctx)?;
+    init_skills(ctx)?;
+    init_status_effects(ctx)?;
+    init_items(ctx)?;
+    init_equipment(ctx)?;
+    init_loot(ctx)?;
+    init_shops(ctx)?;
+    init_trades(ctx)?;
+    init_chat(ctx)?;
+    init_parties(ctx)?;
+    init_guilds(ctx)?;
 
     Ok(())
 }
@@ -0,0 +1,345 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{Guild, GuildInvite, GuildInviteCleanupTimer, GuildMember, GuildPermissions, GuildRank};
+use crate::config::*;
+use crate::reducers::character_reducers::spend_mesos;
+use crate::reducers::chat_reducers::word_filter;
+use crate::reducers::moderation_reducers::require_scheduler;
+// Import table access traits
+use crate::tables::guild::{guild, guild_rank, guild_member, guild_invite};
+use crate::tables::player::player;
+use crate::tables::timers::guild_invite_cleanup_timer;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+const MASTER_RANK: u32 = 1;
+
+// Schedule expired invitation cleanup
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_guilds(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.guild_invite_cleanup_timer().try_insert(GuildInviteCleanupTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(GUILD_INVITE_CLEANUP_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Found a guild led by the caller, paying GUILD_CREATION_COST mesos
+#[reducer]
+pub fn create_guild(ctx: &ReducerContext, name: String) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    if guild_of(ctx, player.player_id).is_some() {
+        return Err("You are already in a guild".into());
+    }
+
+    let name = name.trim().to_string();
+    let (min_length, max_length) = GUILD_NAME_LENGTH;
+    let length = name.chars().count();
+    if length < min_length || length > max_length || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Guild names are {} to {} letters or digits", min_length, max_length));
+    }
+    if ctx.db.guild().iter().any(|guild| guild.name.eq_ignore_ascii_case(&name)) {
+        return Err(format!("{} is already taken", name));
+    }
+
+    spend_mesos(ctx, player.player_id, GUILD_CREATION_COST)?;
+
+    let guild = ctx.db.guild().insert(Guild {
+        guild_id: 0, // Auto-incremented
+        name,
+        master_player_id: player.player_id,
+        notice: String::new(),
+        created_at: ctx.timestamp,
+    });
+
+    let officer = GuildPermissions { invite: true, kick: true, promote: true, edit_notice: true };
+    let ranks = [
+        (MASTER_RANK, "Master", officer),
+        (2, "Jr. Master", officer),
+        (3, "Member", GuildPermissions::default()),
+    ];
+    for (rank, title, permissions) in ranks {
+        ctx.db.guild_rank().insert(GuildRank {
+            rank_id: 0, // Auto-incremented
+            guild_id: guild.guild_id,
+            rank,
+            title: title.into(),
+            permissions,
+        });
+    }
+
+    ctx.db.guild_member().insert(GuildMember {
+        player_id: player.player_id,
+        guild_id: guild.guild_id,
+        rank: MASTER_RANK,
+        name: player.name,
+        joined_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// Invite an online player into the caller's guild
+#[reducer]
+pub fn invite_to_guild(ctx: &ReducerContext, invitee_player_id: u32) -> Result<(), String> {
+    let actor = acting_member(ctx, |permissions| permissions.invite)?;
+    if ctx.db.player().player_id().find(invitee_player_id).is_none() {
+        return Err("That player is not online".into());
+    }
+    if guild_of(ctx, invitee_player_id).is_some() {
+        return Err("That player is already in a guild".into());
+    }
+    if member_count(ctx, actor.guild_id) >= GUILD_MAX_MEMBERS {
+        return Err("Your guild is full".into());
+    }
+
+    // A fresh invitation replaces an older one from the same guild
+    let previous: Vec<u64> = ctx.db.guild_invite().invitee_player_id().filter(invitee_player_id)
+        .filter(|invite| invite.guild_id == actor.guild_id)
+        .map(|invite| invite.invite_id)
+        .collect();
+    for invite_id in previous {
+        ctx.db.guild_invite().invite_id().delete(invite_id);
+    }
+
+    ctx.db.guild_invite().insert(GuildInvite {
+        invite_id: 0, // Auto-incremented
+        guild_id: actor.guild_id,
+        inviter_player_id: actor.player_id,
+        invitee_player_id,
+        created_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// Join the guild of an invitation addressed to the caller, at the lowest rank
+#[reducer]
+pub fn accept_guild_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let invite = ctx.db.guild_invite().invite_id().find(invite_id)
+        .filter(|invite| invite.invitee_player_id == player.player_id)
+        .ok_or("Invitation not found")?;
+
+    let timeout = Duration::from_millis(GUILD_INVITE_TIMEOUT_MS);
+    if ctx.timestamp.duration_since(invite.created_at).is_some_and(|age| age >= timeout) {
+        ctx.db.guild_invite().invite_id().delete(invite_id);
+        return Err("That invitation has expired".into());
+    }
+    if guild_of(ctx, player.player_id).is_some() {
+        return Err("You are already in a guild".into());
+    }
+    if ctx.db.guild().guild_id().find(invite.guild_id).is_none() {
+        ctx.db.guild_invite().invite_id().delete(invite_id);
+        return Err("That guild no longer exists".into());
+    }
+    if member_count(ctx, invite.guild_id) >= GUILD_MAX_MEMBERS {
+        return Err("That guild is full".into());
+    }
+
+    let lowest_rank = ctx.db.guild_rank().guild_id().filter(invite.guild_id)
+        .map(|rank| rank.rank)
+        .max()
+        .ok_or("That guild has no ranks")?;
+    ctx.db.guild_member().insert(GuildMember {
+        player_id: player.player_id,
+        guild_id: invite.guild_id,
+        rank: lowest_rank,
+        name: player.name,
+        joined_at: ctx.timestamp,
+    });
+    ctx.db.guild_invite().invitee_player_id().delete(player.player_id);
+
+    Ok(())
+}
+
+// Turn down an invitation addressed to the caller
+#[reducer]
+pub fn decline_guild_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    ctx.db.guild_invite().invite_id().find(invite_id)
+        .filter(|invite| invite.invitee_player_id == player.player_id)
+        .ok_or("Invitation not found")?;
+
+    ctx.db.guild_invite().invite_id().delete(invite_id);
+
+    Ok(())
+}
+
+// Leave the caller's guild; the master must hand over mastership or disband instead
+#[reducer]
+pub fn leave_guild(ctx: &ReducerContext) -> Result<(), String> {
+    let member = acting_member(ctx, |_| true)?;
+    if member.rank == MASTER_RANK {
+        return Err("The guild master must transfer mastership or disband the guild".into());
+    }
+
+    ctx.db.guild_member().player_id().delete(member.player_id);
+
+    Ok(())
+}
+
+// Remove a lower-ranked member from the caller's guild
+#[reducer]
+pub fn kick_from_guild(ctx: &ReducerContext, player_id: u32) -> Result<(), String> {
+    let actor = acting_member(ctx, |permissions| permissions.kick)?;
+    let target = subordinate(ctx, &actor, player_id)?;
+
+    ctx.db.guild_member().player_id().delete(target.player_id);
+
+    Ok(())
+}
+
+// Promote or demote a lower-ranked member to a rank below the caller's own
+// The master may also pass mastership on by giving rank 1, stepping down to rank 2.
+#[reducer]
+pub fn set_guild_rank(ctx: &ReducerContext, player_id: u32, rank: u32) -> Result<(), String> {
+    let actor = acting_member(ctx, |permissions| permissions.promote)?;
+    let mut target = subordinate(ctx, &actor, player_id)?;
+    if !ctx.db.guild_rank().guild_id().filter(actor.guild_id).any(|guild_rank| guild_rank.rank == rank) {
+        return Err(format!("Rank {} does not exist", rank));
+    }
+
+    if rank == MASTER_RANK {
+        if actor.rank != MASTER_RANK {
+            return Err("Only the guild master can pass on mastership".into());
+        }
+        let mut guild = ctx.db.guild().guild_id().find(actor.guild_id)
+            .ok_or("Guild not found")?;
+        guild.master_player_id = target.player_id;
+        ctx.db.guild().guild_id().update(guild);
+
+        let mut former_master = actor;
+        former_master.rank = MASTER_RANK + 1;
+        ctx.db.guild_member().player_id().update(former_master);
+    } else if rank <= actor.rank {
+        return Err("You can only assign ranks below your own".into());
+    }
+
+    target.rank = rank;
+    if let Some(online) = ctx.db.player().player_id().find(target.player_id) {
+        target.name = online.name;
+    }
+    ctx.db.guild_member().player_id().update(target);
+
+    Ok(())
+}
+
+// Replace the guild notice shown to every member
+#[reducer]
+pub fn set_guild_notice(ctx: &ReducerContext, notice: String) -> Result<(), String> {
+    let actor = acting_member(ctx, |permissions| permissions.edit_notice)?;
+
+    let notice: String = notice.trim().chars().filter(|c| !c.is_control()).collect();
+    if notice.chars().count() > GUILD_NOTICE_MAX_LENGTH {
+        return Err(format!("Notices are limited to {} characters", GUILD_NOTICE_MAX_LENGTH));
+    }
+
+    let mut guild = ctx.db.guild().guild_id().find(actor.guild_id)
+        .ok_or("Guild not found")?;
+    guild.notice = word_filter().filter(&notice);
+    ctx.db.guild().guild_id().update(guild);
+
+    Ok(())
+}
+
+// Rename a rank and change what it may do (master only)
+// The master rank always keeps every permission, so only its title can change.
+#[reducer]
+pub fn edit_guild_rank(ctx: &ReducerContext, rank: u32, title: String, permissions: GuildPermissions) -> Result<(), String> {
+    let actor = acting_member(ctx, |_| true)?;
+    if actor.rank != MASTER_RANK {
+        return Err("Only the guild master can edit ranks".into());
+    }
+    let mut guild_rank = ctx.db.guild_rank().guild_id().filter(actor.guild_id)
+        .find(|guild_rank| guild_rank.rank == rank)
+        .ok_or(format!("Rank {} does not exist", rank))?;
+
+    let title: String = title.trim().chars().filter(|c| !c.is_control()).collect();
+    if title.is_empty() || title.chars().count() > GUILD_RANK_TITLE_MAX_LENGTH {
+        return Err(format!("Rank titles are 1 to {} characters", GUILD_RANK_TITLE_MAX_LENGTH));
+    }
+
+    guild_rank.title = word_filter().filter(&title);
+    if rank != MASTER_RANK {
+        guild_rank.permissions = permissions;
+    }
+    ctx.db.guild_rank().rank_id().update(guild_rank);
+
+    Ok(())
+}
+
+// Dissolve the caller's guild (master only)
+#[reducer]
+pub fn disband_guild(ctx: &ReducerContext) -> Result<(), String> {
+    let actor = acting_member(ctx, |_| true)?;
+    if actor.rank != MASTER_RANK {
+        return Err("Only the guild master can disband the guild".into());
+    }
+
+    let guild_id = actor.guild_id;
+    ctx.db.guild_member().guild_id().delete(guild_id);
+    ctx.db.guild_rank().guild_id().delete(guild_id);
+    ctx.db.guild_invite().guild_id().delete(guild_id);
+    ctx.db.guild().guild_id().delete(guild_id);
+
+    Ok(())
+}
+
+// Delete invitations older than GUILD_INVITE_TIMEOUT_MS
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn expire_guild_invites(ctx: &ReducerContext, _timer: GuildInviteCleanupTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let timeout = Duration::from_millis(GUILD_INVITE_TIMEOUT_MS);
+    let expired: Vec<u64> = ctx.db.guild_invite().iter()
+        .filter(|invite| ctx.timestamp.duration_since(invite.created_at).is_some_and(|age| age >= timeout))
+        .map(|invite| invite.invite_id)
+        .collect();
+    for invite_id in expired {
+        ctx.db.guild_invite().invite_id().delete(invite_id);
+    }
+
+    Ok(())
+}
+
+pub fn guild_of(ctx: &ReducerContext, player_id: u32) -> Option<u32> {
+    ctx.db.guild_member().player_id().find(player_id).map(|member| member.guild_id)
+}
+
+fn member_count(ctx: &ReducerContext, guild_id: u32) -> usize {
+    ctx.db.guild_member().guild_id().filter(guild_id).count()
+}
+
+// The caller's membership, if their rank grants the permission checked by `allowed`
+fn acting_member(ctx: &ReducerContext, allowed: fn(&GuildPermissions) -> bool) -> Result<GuildMember, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let member = ctx.db.guild_member().player_id().find(player.player_id)
+        .ok_or("You are not in a guild")?;
+
+    let permissions = ctx.db.guild_rank().guild_id().filter(member.guild_id)
+        .find(|rank| rank.rank == member.rank)
+        .map(|rank| rank.permissions)
+        .unwrap_or_default();
+    if !allowed(&permissions) {
+        return Err("Your guild rank does not allow that".into());
+    }
+    Ok(member)
+}
+
+// A member of the actor's guild ranked strictly below the actor
+fn subordinate(ctx: &ReducerContext, actor: &GuildMember, player_id: u32) -> Result<GuildMember, String> {
+    let target = ctx.db.guild_member().player_id().find(player_id)
+        .filter(|member| member.guild_id == actor.guild_id)
+        .ok_or("That player is not in your guild")?;
+    if target.rank <= actor.rank {
+        return Err("You can only manage members ranked below you".into());
+    }
+    Ok(target)
+}
@@ -0,0 +1,257 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{InventoryItem, InventoryTab, ItemTemplate};
+use crate::config::*;
+use crate::reducers::combat_reducers::living_player_entity;
+use crate::reducers::entity_reducers::spawn_item_drop;
+use crate::reducers::physics_reducers::with_physics_world;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::item::{item_template, inventory_item};
+use crate::tables::player::player;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed starter item data
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_items(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    // (item, name, tab, max stack, sell price, rechargeable)
+    let items = [
+        (2000000, "Red Potion", InventoryTab::Use, 100, 25, false),
+        (2000001, "Orange Potion", InventoryTab::Use, 100, 80, false),
+        (2000003, "Blue Potion", InventoryTab::Use, 100, 100, false),
+        (2070000, "Subi Throwing-Stars", InventoryTab::Use, 800, 250, true),
+        (3010000, "The Relaxer", InventoryTab::Setup, 1, 1, false),
+        (4000001, "Orange Mushroom Cap", InventoryTab::Etc, 200, 10, false),
+        (4000019, "Snail Shell", InventoryTab::Etc, 200, 1, false),
+    ];
+    for (item_id, name, tab, max_stack, price, rechargeable) in items {
+        ctx.db.item_template().try_insert(ItemTemplate {
+            item_id,
+            name: name.into(),
+            tab,
+            max_stack,
+            price,
+            rechargeable,
+        })?;
+    }
+
+    Ok(())
+}
+
+// Move a stack to another slot of the same tab
+// Onto an empty slot it moves; onto the same stackable item it merges as much as fits; otherwise the two swap.
+#[reducer]
+pub fn move_item(ctx: &ReducerContext, tab: InventoryTab, from_slot: u32, to_slot: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    validate_slot(to_slot)?;
+    if from_slot == to_slot {
+        return Err("Source and destination are the same slot".into());
+    }
+
+    let mut source = item_at(ctx, player.player_id, tab, from_slot)
+        .ok_or("No item in the source slot")?;
+
+    match item_at(ctx, player.player_id, tab, to_slot) {
+        None => {
+            source.slot = to_slot;
+            ctx.db.inventory_item().inventory_item_id().update(source);
+        }
+        Some(mut destination) if destination.item_id == source.item_id && max_stack(ctx, source.item_id) > 1 => {
+            let room = max_stack(ctx, source.item_id).saturating_sub(destination.quantity);
+            if room == 0 {
+                return Err("Destination stack is full".into());
+            }
+            let moved = room.min(source.quantity);
+            destination.quantity += moved;
+            ctx.db.inventory_item().inventory_item_id().update(destination);
+
+            if moved == source.quantity {
+                ctx.db.inventory_item().inventory_item_id().delete(source.inventory_item_id);
+            } else {
+                source.quantity -= moved;
+                ctx.db.inventory_item().inventory_item_id().update(source);
+            }
+        }
+        Some(mut destination) => {
+            destination.slot = from_slot;
+            source.slot = to_slot;
+            ctx.db.inventory_item().inventory_item_id().update(destination);
+            ctx.db.inventory_item().inventory_item_id().update(source);
+        }
+    }
+
+    Ok(())
+}
+
+// Split `quantity` off a stack into an empty slot of the same tab
+#[reducer]
+pub fn split_item(ctx: &ReducerContext, tab: InventoryTab, slot: u32, quantity: u32, to_slot: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    validate_slot(to_slot)?;
+
+    let mut source = item_at(ctx, player.player_id, tab, slot)
+        .ok_or("No item in the source slot")?;
+    if quantity == 0 || quantity >= source.quantity {
+        return Err("Split quantity must be less than the stack size".into());
+    }
+    if item_at(ctx, player.player_id, tab, to_slot).is_some() {
+        return Err("Destination slot is not empty".into());
+    }
+
+    source.quantity -= quantity;
+    let item_id = source.item_id;
+    ctx.db.inventory_item().inventory_item_id().update(source);
+    ctx.db.inventory_item().insert(InventoryItem {
+        inventory_item_id: 0, // Auto-incremented
+        player_id: player.player_id,
+        tab,
+        slot: to_slot,
+        item_id,
+        quantity,
+    });
+
+    Ok(())
+}
+
+// Drop `quantity` from a stack onto the ground at the player's feet
+#[reducer]
+pub fn drop_item(ctx: &ReducerContext, tab: InventoryTab, slot: u32, quantity: u32) -> Result<(), String> {
+    let (player_id, entity_id) = living_player_entity(ctx)?;
+    let entity = ctx.db.entity().entity_id().find(entity_id)
+        .ok_or("No spawned entity")?;
+
+    let item_id = take_from_slot(ctx, player_id, tab, slot, quantity)?;
+
+    with_physics_world(|physics_world| {
+        spawn_item_drop(ctx, physics_world, item_id, quantity, entity.map_id, entity.position, None);
+    }).ok_or("Physics world not initialized")?;
+
+    Ok(())
+}
+
+// Merge partial stacks and pack a tab's items into the first slots, ordered by item id
+#[reducer]
+pub fn sort_inventory(ctx: &ReducerContext, tab: InventoryTab) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    let mut stacks: Vec<InventoryItem> = ctx.db.inventory_item().player_id().filter(player.player_id)
+        .filter(|item| item.tab == tab)
+        .collect();
+    stacks.sort_by_key(|item| (item.item_id, item.slot));
+
+    // Rebuild the tab from the sorted totals
+    let mut sorted: Vec<(u32, u32)> = Vec::new();
+    for stack in &stacks {
+        let limit = max_stack(ctx, stack.item_id);
+        let mut remaining = stack.quantity;
+        if let Some((item_id, quantity)) = sorted.last_mut() {
+            if *item_id == stack.item_id && *quantity < limit {
+                let moved = (limit - *quantity).min(remaining);
+                *quantity += moved;
+                remaining -= moved;
+            }
+        }
+        if remaining > 0 {
+            sorted.push((stack.item_id, remaining));
+        }
+    }
+
+    for stack in stacks {
+        ctx.db.inventory_item().inventory_item_id().delete(stack.inventory_item_id);
+    }
+    for (slot, (item_id, quantity)) in sorted.into_iter().enumerate() {
+        ctx.db.inventory_item().insert(InventoryItem {
+            inventory_item_id: 0, // Auto-incremented
+            player_id: player.player_id,
+            tab,
+            slot: slot as u32,
+            item_id,
+            quantity,
+        });
+    }
+
+    Ok(())
+}
+
+// Add items to a character's inventory, topping up existing stacks before using empty slots
+// Fails when they do not all fit; the caller's reducer must then return Err so nothing is kept.
+pub fn add_item(ctx: &ReducerContext, player_id: u32, item_id: u32, quantity: u32) -> Result<(), String> {
+    let template = ctx.db.item_template().item_id().find(item_id)
+        .ok_or(format!("Unknown item {}", item_id))?;
+    let mut remaining = quantity;
+
+    let partial_stacks: Vec<InventoryItem> = ctx.db.inventory_item().player_id().filter(player_id)
+        .filter(|item| item.item_id == item_id && item.quantity < template.max_stack)
+        .collect();
+    for mut stack in partial_stacks {
+        if remaining == 0 {
+            break;
+        }
+        let moved = (template.max_stack - stack.quantity).min(remaining);
+        stack.quantity += moved;
+        remaining -= moved;
+        ctx.db.inventory_item().inventory_item_id().update(stack);
+    }
+
+    while remaining > 0 {
+        let slot = first_free_slot(ctx, player_id, template.tab)
+            .ok_or(format!("{:?} inventory is full", template.tab))?;
+        let placed = remaining.min(template.max_stack.max(1));
+        ctx.db.inventory_item().insert(InventoryItem {
+            inventory_item_id: 0, // Auto-incremented
+            player_id,
+            tab: template.tab,
+            slot,
+            item_id,
+            quantity: placed,
+        });
+        remaining -= placed;
+    }
+
+    Ok(())
+}
+
+// Remove `quantity` from the stack in a slot, deleting it when emptied; returns the item id
+pub fn take_from_slot(ctx: &ReducerContext, player_id: u32, tab: InventoryTab, slot: u32, quantity: u32) -> Result<u32, String> {
+    let mut stack = item_at(ctx, player_id, tab, slot)
+        .ok_or("No item in that slot")?;
+    if quantity == 0 || quantity > stack.quantity {
+        return Err(format!("Cannot take {} of {}", quantity, stack.quantity));
+    }
+
+    let item_id = stack.item_id;
+    if quantity == stack.quantity {
+        ctx.db.inventory_item().inventory_item_id().delete(stack.inventory_item_id);
+    } else {
+        stack.quantity -= quantity;
+        ctx.db.inventory_item().inventory_item_id().update(stack);
+    }
+    Ok(item_id)
+}
+
+pub fn item_at(ctx: &ReducerContext, player_id: u32, tab: InventoryTab, slot: u32) -> Option<InventoryItem> {
+    ctx.db.inventory_item().player_id().filter(player_id)
+        .find(|item| item.tab == tab && item.slot == slot)
+}
+
+pub fn first_free_slot(ctx: &ReducerContext, player_id: u32, tab: InventoryTab) -> Option<u32> {
+    let used: Vec<u32> = ctx.db.inventory_item().player_id().filter(player_id)
+        .filter(|item| item.tab == tab)
+        .map(|item| item.slot)
+        .collect();
+    (0..INVENTORY_SLOTS_PER_TAB).find(|slot| !used.contains(slot))
+}
+
+fn validate_slot(slot: u32) -> Result<(), String> {
+    if slot >= INVENTORY_SLOTS_PER_TAB {
+        return Err(format!("Slot {} is out of range", slot));
+    }
+    Ok(())
+}
+
+fn max_stack(ctx: &ReducerContext, item_id: u32) -> u32 {
+    ctx.db.item_template().item_id().find(item_id).map_or(1, |template| template.max_stack)
+}
@@ -0,0 +1,175 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{Job, QuestCompletion, StatKind};
+use crate::config::*;
+use crate::reducers::character_reducers::{refresh_character_stats, stat_value};
+use crate::reducers::moderation_reducers::require_admin;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::job::job;
+use crate::tables::player::player;
+use crate::tables::quest::quest_completion;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed the job tree: Beginner, the five first jobs and one second job per branch
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_jobs(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.job().try_insert(Job {
+        job_id: BEGINNER_JOB_ID,
+        name: "Beginner".into(),
+        parent_job_id: None,
+        advancement: 0,
+        required_level: 1,
+        required_stat: StatKind::Str,
+        required_stat_value: 0,
+        required_quest_id: None,
+        primary_stat: StatKind::Str,
+        secondary_stat: StatKind::Dex,
+        hp_per_level: HP_PER_LEVEL,
+        mp_per_level: MP_PER_LEVEL,
+        hp_bonus: 0,
+        mp_bonus: 0,
+        sp_bonus: 0,
+    })?;
+
+    // (job_id, name, level, required stat, primary, secondary, HP/MP per level, HP/MP bonus)
+    let first_jobs = [
+        (100, "Warrior", 10, (StatKind::Str, 35), StatKind::Str, StatKind::Dex, (24, 4), (200, 0)),
+        (200, "Magician", 8, (StatKind::Int, 20), StatKind::Int, StatKind::Luk, (10, 22), (0, 100)),
+        (300, "Bowman", 10, (StatKind::Dex, 25), StatKind::Dex, StatKind::Str, (16, 10), (100, 25)),
+        (400, "Thief", 10, (StatKind::Dex, 25), StatKind::Luk, StatKind::Dex, (16, 10), (100, 25)),
+        (500, "Pirate", 10, (StatKind::Dex, 20), StatKind::Dex, StatKind::Str, (18, 12), (150, 25)),
+    ];
+    for (job_id, name, required_level, (required_stat, required_stat_value), primary_stat, secondary_stat, (hp_per_level, mp_per_level), (hp_bonus, mp_bonus)) in first_jobs {
+        ctx.db.job().try_insert(Job {
+            job_id,
+            name: name.into(),
+            parent_job_id: Some(BEGINNER_JOB_ID),
+            advancement: 1,
+            required_level,
+            required_stat,
+            required_stat_value,
+            required_quest_id: None,
+            primary_stat,
+            secondary_stat,
+            hp_per_level,
+            mp_per_level,
+            hp_bonus,
+            mp_bonus,
+            sp_bonus: 1,
+        })?;
+    }
+
+    // Second jobs keep their parent's stats and growth, and require the parent's advancement quest
+    let second_jobs = [
+        (110, "Fighter", 100),
+        (210, "Wizard", 200),
+        (310, "Hunter", 300),
+        (410, "Assassin", 400),
+        (510, "Brawler", 500),
+    ];
+    for (job_id, name, parent_job_id) in second_jobs {
+        let parent = ctx.db.job().job_id().find(parent_job_id)
+            .ok_or_else(|| format!("Missing parent job {}", parent_job_id))?;
+        ctx.db.job().try_insert(Job {
+            job_id,
+            name: name.into(),
+            parent_job_id: Some(parent_job_id),
+            advancement: 2,
+            required_level: 30,
+            required_stat: parent.required_stat,
+            required_stat_value: parent.required_stat_value,
+            required_quest_id: Some(SECOND_JOB_QUEST_BASE + parent_job_id),
+            primary_stat: parent.primary_stat,
+            secondary_stat: parent.secondary_stat,
+            hp_per_level: parent.hp_per_level + 2,
+            mp_per_level: parent.mp_per_level + 2,
+            hp_bonus: parent.hp_bonus + 100,
+            mp_bonus: parent.mp_bonus + 50,
+            sp_bonus: 1,
+        })?;
+    }
+
+    Ok(())
+}
+
+// Advance the caller's character into `job_id`
+// The target job must branch from the current one, and its level, stat and quest requirements must be met.
+#[reducer]
+pub fn advance_job(ctx: &ReducerContext, job_id: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let mut character = ctx.db.character_stats().player_id().find(player.player_id)
+        .ok_or("Character not found")?;
+    let job = ctx.db.job().job_id().find(job_id)
+        .ok_or(format!("Unknown job {}", job_id))?;
+
+    if job.parent_job_id != Some(character.job_id) {
+        return Err(format!("{} cannot be reached from the current job", job.name));
+    }
+    if character.level < job.required_level {
+        return Err(format!("{} requires level {}", job.name, job.required_level));
+    }
+    if stat_value(&character, job.required_stat) < job.required_stat_value {
+        return Err(format!("{} requires {:?} {}", job.name, job.required_stat, job.required_stat_value));
+    }
+    if let Some(quest_id) = job.required_quest_id {
+        if !has_completed_quest(ctx, player.player_id, quest_id) {
+            return Err(format!("{} requires quest {}", job.name, quest_id));
+        }
+    }
+
+    character.job_id = job.job_id;
+    character.max_hp += job.hp_bonus;
+    character.max_mp += job.mp_bonus;
+    character.sp += job.sp_bonus;
+    log::info!("Player {} advanced to {}", player.player_id, job.name);
+
+    refresh_character_stats(ctx, &character);
+    ctx.db.character_stats().player_id().update(character);
+
+    Ok(())
+}
+
+// Record a quest as completed for a player (administrators only)
+// Stands in for the quest system until one exists.
+#[reducer]
+pub fn complete_quest(ctx: &ReducerContext, player_id: u32, quest_id: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+
+    if ctx.db.character_stats().player_id().find(player_id).is_none() {
+        return Err(format!("Player {} has no character", player_id));
+    }
+    if has_completed_quest(ctx, player_id, quest_id) {
+        return Err(format!("Quest {} already completed", quest_id));
+    }
+
+    ctx.db.quest_completion().insert(QuestCompletion {
+        completion_id: 0, // Auto-incremented
+        player_id,
+        quest_id,
+        completed_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// A job and every job it was advanced from, newest first
+pub fn job_lineage(ctx: &ReducerContext, job_id: u32) -> Vec<u32> {
+    let mut lineage = vec![job_id];
+    let mut current = job_id;
+    while let Some(parent_job_id) = ctx.db.job().job_id().find(current).and_then(|job| job.parent_job_id) {
+        // Guard against a malformed tree looping back on itself
+        if lineage.contains(&parent_job_id) {
+            break;
+        }
+        lineage.push(parent_job_id);
+        current = parent_job_id;
+    }
+    lineage
+}
+
+pub fn has_completed_quest(ctx: &ReducerContext, player_id: u32, quest_id: u32) -> bool {
+    ctx.db.quest_completion().player_id().filter(player_id)
+        .any(|completion| completion.quest_id == quest_id)
+}
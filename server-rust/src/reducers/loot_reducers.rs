@@ -0,0 +1,145 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{DropEntry, DropKind, DropRule, Entity, ItemDropExpiryTimer};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::utils::roll_drop_table;
+use crate::reducers::character_reducers::add_mesos;
+use crate::reducers::combat_reducers::living_player_entity;
+use crate::reducers::entity_reducers::{despawn_entity, despawn_entity_outside_step, spawn_item_drop};
+use crate::reducers::inventory_reducers::add_item;
+use crate::reducers::moderation_reducers::require_scheduler;
+use crate::reducers::party_reducers::party_of;
+use crate::reducers::physics_reducers::with_physics_world;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::item_drop::item_drop;
+use crate::tables::loot::drop_entry;
+use crate::tables::monster::{monster, monster_template};
+use crate::tables::player::player_entity;
+use crate::tables::timers::item_drop_expiry_timer;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed drop tables and schedule drop expiry
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_loot(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    // (table, kind, rule, min quantity, max quantity)
+    let entries = [
+        // Shared potion table, nested into monster tables
+        (COMMON_POTION_TABLE_ID, DropKind::Item(2000000), DropRule::Weighted(5), 1, 1),    // Red Potion
+        (COMMON_POTION_TABLE_ID, DropKind::Item(2000001), DropRule::Weighted(3), 1, 1),    // Orange Potion
+        (COMMON_POTION_TABLE_ID, DropKind::Item(2000003), DropRule::Weighted(2), 1, 1),    // Blue Potion
+        // Snail
+        (100100, DropKind::Mesos, DropRule::Guaranteed, 1, 5),
+        (100100, DropKind::Item(4000019), DropRule::Chance(0.6), 1, 1),                   // Snail Shell
+        (100100, DropKind::Table(COMMON_POTION_TABLE_ID), DropRule::Chance(0.05), 1, 1),
+        // Orange Mushroom
+        (1210102, DropKind::Mesos, DropRule::Guaranteed, 8, 20),
+        (1210102, DropKind::Item(4000001), DropRule::Chance(0.5), 1, 1),                  // Orange Mushroom Cap
+        (1210102, DropKind::Table(COMMON_POTION_TABLE_ID), DropRule::Chance(0.1), 1, 2),
+        (1210102, DropKind::Nothing, DropRule::Weighted(97), 1, 1),
+        (1210102, DropKind::Item(1002008), DropRule::Weighted(2), 1, 1),                  // Brown Skullcap
+        (1210102, DropKind::Item(1302000), DropRule::Weighted(1), 1, 1),                  // Sword
+    ];
+    for (table_id, kind, rule, min_quantity, max_quantity) in entries {
+        ctx.db.drop_entry().try_insert(DropEntry {
+            entry_id: 0, // Auto-incremented
+            table_id,
+            kind,
+            rule,
+            min_quantity,
+            max_quantity,
+        })?;
+    }
+
+    ctx.db.item_drop_expiry_timer().try_insert(ItemDropExpiryTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(DROP_EXPIRY_CHECK_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Roll a dead monster's drop table and scatter the drops where it fell
+// The killer's player owns them for DROP_OWNERSHIP_MS. Must run before the monster is despawned.
+pub fn drop_loot(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, monster_entity: &Entity, killer_entity_id: u32) {
+    let Some(table_id) = ctx.db.monster().entity_id().find(monster_entity.entity_id)
+        .and_then(|monster| ctx.db.monster_template().template_id().find(monster.template_id))
+        .and_then(|template| template.drop_table_id) else {
+        return;
+    };
+    let owner_player_id = ctx.db.player_entity().entity_id().find(killer_entity_id)
+        .map(|killer| killer.player_id);
+
+    let loot = roll_drop_table(&mut ctx.rng(), ctx, table_id);
+
+    // Fan drops out around the body: 0, +1, -1, +2, -2, ...
+    for (index, (item_id, quantity)) in loot.into_iter().enumerate() {
+        let step = index.div_ceil(2) as f32 * if index % 2 == 0 { -1.0 } else { 1.0 };
+        let drop = spawn_item_drop(ctx, physics_world, item_id, quantity, monster_entity.map_id, monster_entity.position, owner_player_id);
+        let velocity = DbVector2::new(step * DROP_SPREAD_SPEED, DROP_POP_SPEED);
+        physics_world.apply_knockback(drop.entity_id, velocity.to_nalgebra());
+    }
+}
+
+// Loot a drop within reach of the caller
+// Mesos go straight onto the character; items must fit in the inventory.
+#[reducer]
+pub fn pickup_item(ctx: &ReducerContext, drop_entity_id: u32) -> Result<(), String> {
+    let (player_id, entity_id) = living_player_entity(ctx)?;
+    let player_entity = ctx.db.entity().entity_id().find(entity_id)
+        .ok_or("No spawned entity")?;
+    let drop = ctx.db.item_drop().entity_id().find(drop_entity_id)
+        .ok_or("That drop is gone")?;
+    let drop_entity = ctx.db.entity().entity_id().find(drop_entity_id)
+        .ok_or("That drop is gone")?;
+
+    if drop_entity.map_id != player_entity.map_id {
+        return Err("That drop is on another map".into());
+    }
+    // The owner and their party share the exclusivity window
+    let owned_by_other = drop.owner_player_id.is_some_and(|owner| {
+        owner != player_id && (party_of(ctx, owner).is_none() || party_of(ctx, owner) != party_of(ctx, player_id))
+    });
+    if ctx.timestamp < drop.exclusive_until && owned_by_other {
+        return Err("That drop belongs to someone else".into());
+    }
+
+    with_physics_world(|physics_world| {
+        // Range is checked against live physics positions, which may be ahead of the last sync
+        let position_of = |id: u32, fallback: DbVector2| physics_world.get_body_state(id)
+            .map_or(fallback, |(position, _)| DbVector2::from_nalgebra(position));
+        let distance = (position_of(entity_id, player_entity.position) - position_of(drop_entity_id, drop_entity.position)).magnitude();
+        if distance > ITEM_PICKUP_RANGE {
+            return Err("Too far away to pick that up".to_string());
+        }
+
+        if drop.item_id == MESO_ITEM_ID {
+            add_mesos(ctx, player_id, drop.quantity as u64)?;
+        } else {
+            add_item(ctx, player_id, drop.item_id, drop.quantity)?;
+        }
+
+        despawn_entity(ctx, physics_world, drop_entity_id);
+        Ok(())
+    }).ok_or("Physics world not initialized")?
+}
+
+// Despawn drops whose lifetime has run out
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn expire_item_drops(ctx: &ReducerContext, _timer: ItemDropExpiryTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let expired: Vec<u32> = ctx.db.item_drop().iter()
+        .filter(|drop| drop.expires_at <= ctx.timestamp)
+        .map(|drop| drop.entity_id)
+        .collect();
+    for entity_id in expired {
+        despawn_entity_outside_step(ctx, entity_id);
+    }
+
+    Ok(())
+}
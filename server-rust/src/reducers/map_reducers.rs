@@ -0,0 +1,128 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{Platform, PlatformKind};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::{PhysicsWorld, NavGraph, NavSurface};
+use crate::reducers::moderation_reducers::require_admin;
+use crate::reducers::physics_reducers::with_physics_world;
+// Import table access traits
+use crate::tables::platform::platform;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed the starter map geometry
+// This is called from the main init reducer in game_reducers.rs, before init_physics
+pub fn init_maps(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    let geometry = [
+        // Wide ground platform along the bottom of the 1000x1000 world (top at y = 0)
+        (PlatformKind::Solid, DbVector2::new(500.0, -10.0), DbVector2::new(1000.0, 20.0)),
+        // A small climb: ladder onto a ledge, then a jump to a higher ledge
+        (PlatformKind::Solid, DbVector2::new(650.0, 2.0), DbVector2::new(20.0, 1.0)),
+        (PlatformKind::Solid, DbVector2::new(670.0, 4.5), DbVector2::new(16.0, 1.0)),
+        (PlatformKind::Ladder, DbVector2::new(645.0, 1.25), DbVector2::new(0.5, 2.5)),
+    ];
+
+    for (kind, position, size) in geometry {
+        ctx.db.platform().try_insert(Platform {
+            platform_id: 0, // Auto-incremented
+            map_id: DEFAULT_MAP_ID,
+            kind,
+            position,
+            size,
+        })?;
+    }
+
+    Ok(())
+}
+
+// Build colliders and navigation graphs for every platform
+// Called once the physics world exists.
+pub fn load_map_geometry(ctx: &ReducerContext, physics_world: &mut PhysicsWorld) {
+    let mut map_ids = Vec::new();
+    for platform in ctx.db.platform().iter() {
+        if platform.kind == PlatformKind::Solid {
+            physics_world.create_static_ground(
+                platform.platform_id,
+                platform.position.to_nalgebra(),
+                platform.size.to_nalgebra(),
+            );
+        }
+        if !map_ids.contains(&platform.map_id) {
+            map_ids.push(platform.map_id);
+        }
+    }
+
+    for map_id in map_ids {
+        rebuild_navigation(ctx, physics_world, map_id);
+    }
+}
+
+// Rebuild the navigation graph of a map from its current platforms
+pub fn rebuild_navigation(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, map_id: u32) {
+    let surfaces: Vec<NavSurface> = ctx.db.platform().map_id().filter(map_id)
+        .map(|platform| {
+            let half = platform.size / 2.0;
+            let is_ladder = platform.kind == PlatformKind::Ladder;
+            NavSurface {
+                platform_id: platform.platform_id,
+                left: platform.position.x - half.x,
+                right: platform.position.x + half.x,
+                top: platform.position.y + half.y,
+                bottom: if is_ladder { platform.position.y - half.y } else { platform.position.y + half.y },
+                is_ladder,
+            }
+        })
+        .collect();
+
+    log::info!("Rebuilding navigation for map {} from {} surfaces", map_id, surfaces.len());
+    physics_world.nav_graphs.insert(map_id, NavGraph::build(&surfaces));
+}
+
+// Add a platform to a map; colliders and navigation update immediately
+#[reducer]
+pub fn add_platform(
+    ctx: &ReducerContext,
+    map_id: u32,
+    kind: PlatformKind,
+    position: DbVector2,
+    size: DbVector2,
+) -> Result<(), String> {
+    require_admin(ctx)?;
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return Err("Platform size must be positive".into());
+    }
+
+    let platform = ctx.db.platform().insert(Platform {
+        platform_id: 0, // Auto-incremented
+        map_id,
+        kind,
+        position,
+        size,
+    });
+
+    with_physics_world(|physics_world| {
+        if platform.kind == PlatformKind::Solid {
+            physics_world.create_static_ground(platform.platform_id, position.to_nalgebra(), size.to_nalgebra());
+        }
+        rebuild_navigation(ctx, physics_world, map_id);
+    });
+
+    Ok(())
+}
+
+// Remove a platform from its map; colliders and navigation update immediately
+#[reducer]
+pub fn remove_platform(ctx: &ReducerContext, platform_id: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    let platform = ctx.db.platform().platform_id().find(platform_id)
+        .ok_or("Platform not found")?;
+
+    ctx.db.platform().platform_id().delete(platform_id);
+
+    with_physics_world(|physics_world| {
+        physics_world.remove_static_ground(platform_id);
+        rebuild_navigation(ctx, physics_world, platform.map_id);
+    });
+
+    Ok(())
+}
@@ -1,9 +1,49 @@
 pub mod connection_reducers;
 pub mod game_reducers;
 pub mod player_reducers;
+pub mod entity_reducers;
+pub mod monster_reducers;
+pub mod monster_ai_reducers;
+pub mod map_reducers;
 pub mod physics_reducers; // NEW: Physics simulation reducers
+pub mod projectile_reducers;
+pub mod moderation_reducers;
+pub mod combat_reducers;
+pub mod character_reducers;
+pub mod job_reducers;
+pub mod skill_reducers;
+pub mod status_effect_reducers;
+pub mod inventory_reducers;
+pub mod equipment_reducers;
+pub mod loot_reducers;
+pub mod shop_reducers;
+pub mod trade_reducers;
+pub mod chat_reducers;
+pub mod party_reducers;
+pub mod guild_reducers;
+pub mod friend_reducers;
 
 pub use connection_reducers::*;
 pub use game_reducers::*;
 pub use player_reducers::*;
-pub use physics_reducers::*;
\ No newline at end of file
+pub use entity_reducers::*;
+pub use monster_reducers::*;
+pub use monster_ai_reducers::*;
+pub use map_reducers::*;
+pub use physics_reducers::*;
+pub use projectile_reducers::*;
+pub use moderation_reducers::*;
+pub use combat_reducers::*;
+pub use character_reducers::*;
+pub use job_reducers::*;
+pub use skill_reducers::*;
+pub use status_effect_reducers::*;
+pub use inventory_reducers::*;
+pub use equipment_reducers::*;
+pub use loot_reducers::*;
+pub use shop_reducers::*;
+pub use trade_reducers::*;
+pub use chat_reducers::*;
+pub use party_reducers::*;
+pub use guild_reducers::*;
+pub use friend_reducers::*;
\ No newline at end of file
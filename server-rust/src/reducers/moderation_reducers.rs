@@ -0,0 +1,180 @@
+use spacetimedb::{reducer, Identity, ReducerContext, Table, ScheduleAt, Timestamp};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::tables::{Admin, RateLimit, RateLimitKind, SuspicionReason, SuspiciousActivity, SuspiciousActivityTimer};
+use crate::config::*;
+// Import table access traits
+use crate::tables::admin::admin;
+use crate::tables::moderation::{rate_limit, suspicious_activity};
+use crate::tables::timers::suspicious_activity_timer;
+
+// Violations counted since the last flush
+// Kept outside the database on purpose: reducers that reject input return Err, which
+// rolls back any table writes they made, so the count would never be persisted.
+// The trade-off is durability: anything recorded since the last flush (at most one
+// SUSPICIOUS_ACTIVITY_WINDOW_MS) is lost if the module restarts or is republished.
+static PENDING_VIOLATIONS: Mutex<Vec<(Identity, SuspicionReason)>> = Mutex::new(Vec::new());
+
+// Rate limited attempts that were rejected after taking a token
+// Kept outside the database for the same reason: the rejecting Err rolls the token back,
+// so try_consume_rate_limit charges these against the bucket instead.
+static FAILED_ATTEMPTS: Mutex<Vec<(Identity, RateLimitKind, Timestamp)>> = Mutex::new(Vec::new());
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+pub fn init_moderation(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    // Whoever published the module administers it
+    ctx.db.admin().try_insert(Admin { identity: ctx.sender })?;
+
+    ctx.db.suspicious_activity_timer().try_insert(SuspiciousActivityTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(SUSPICIOUS_ACTIVITY_WINDOW_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Reject callers that are not administrators
+pub fn require_admin(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.admin().identity().find(ctx.sender).is_none() {
+        return Err("Administrator permission required".into());
+    }
+    Ok(())
+}
+
+// Reject callers other than the module itself, so scheduled reducers cannot be invoked by clients
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+pub fn require_scheduler(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("This reducer may only be invoked by the scheduler".into());
+    }
+    Ok(())
+}
+
+// Bucket capacity and refill rate per second for each rate limited action
+fn rate_limit_params(kind: RateLimitKind) -> (f32, f32) {
+    match kind {
+        RateLimitKind::Input => (INPUT_RATE_LIMIT_BURST, INPUT_RATE_LIMIT_PER_SECOND),
+        RateLimitKind::Chat => (CHAT_RATE_LIMIT_BURST, CHAT_RATE_LIMIT_PER_SECOND),
+        RateLimitKind::Megaphone => (MEGAPHONE_RATE_LIMIT_BURST, MEGAPHONE_RATE_LIMIT_PER_SECOND),
+        RateLimitKind::FriendRequest => (FRIEND_REQUEST_RATE_LIMIT_BURST, FRIEND_REQUEST_RATE_LIMIT_PER_SECOND),
+    }
+}
+
+// Take one token from the sender's bucket for `kind`
+// Returns false when the bucket is empty; the caller decides how to reject.
+pub fn try_consume_rate_limit(ctx: &ReducerContext, kind: RateLimitKind) -> bool {
+    let (capacity, refill_per_second) = rate_limit_params(kind);
+
+    let existing = ctx.db.rate_limit().identity().filter(ctx.sender)
+        .find(|bucket| bucket.kind == kind);
+
+    // Failures before the bucket was last written are already part of its tokens
+    let failed_since = |since: Option<Timestamp>| FAILED_ATTEMPTS.lock().unwrap().iter()
+        .filter(|(identity, failed_kind, at)| {
+            *identity == ctx.sender && *failed_kind == kind && since.is_none_or(|since| *at > since)
+        })
+        .count() as f32;
+
+    let Some(mut bucket) = existing else {
+        let tokens = capacity - failed_since(None);
+        if tokens < 1.0 {
+            return false;
+        }
+        ctx.db.rate_limit().insert(RateLimit {
+            rate_limit_id: 0, // Auto-incremented
+            identity: ctx.sender,
+            kind,
+            tokens: tokens - 1.0,
+            last_refill: ctx.timestamp,
+        });
+        return true;
+    };
+
+    let elapsed = ctx.timestamp.duration_since(bucket.last_refill).unwrap_or_default();
+    let tokens = (bucket.tokens + elapsed.as_secs_f32() * refill_per_second).min(capacity)
+        - failed_since(Some(bucket.last_refill));
+    if tokens < 1.0 {
+        return false;
+    }
+
+    bucket.tokens = tokens - 1.0;
+    bucket.last_refill = ctx.timestamp;
+    ctx.db.rate_limit().rate_limit_id().update(bucket);
+    true
+}
+
+// Charge the token of a rate limited attempt that is about to be rejected with Err
+// Call after a successful try_consume_rate_limit, on every path that fails afterwards.
+pub fn record_failed_attempt(ctx: &ReducerContext, kind: RateLimitKind) {
+    FAILED_ATTEMPTS.lock().unwrap().push((ctx.sender, kind, ctx.timestamp));
+}
+
+// Count a rule violation by the sender; repeat offenders are flagged on the next flush
+pub fn record_violation(ctx: &ReducerContext, reason: SuspicionReason) {
+    PENDING_VIOLATIONS.lock().unwrap().push((ctx.sender, reason));
+}
+
+// Flag identities whose violations in the last window reached the threshold,
+// and drop rate limit buckets that have refilled completely
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn flush_suspicious_activity(ctx: &ReducerContext, _timer: SuspiciousActivityTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let pending = std::mem::take(&mut *PENDING_VIOLATIONS.lock().unwrap());
+
+    let mut counts: HashMap<(Identity, SuspicionReason), u32> = HashMap::new();
+    for key in pending {
+        *counts.entry(key).or_default() += 1;
+    }
+
+    for ((identity, reason), violations) in counts {
+        if violations < SUSPICIOUS_VIOLATION_THRESHOLD {
+            continue;
+        }
+
+        log::warn!("Flagging {:?} for {:?} ({} violations)", identity, reason, violations);
+
+        let existing = ctx.db.suspicious_activity().identity().filter(identity)
+            .find(|activity| activity.reason == reason);
+        if let Some(mut activity) = existing {
+            activity.violations += violations;
+            activity.times_flagged += 1;
+            activity.last_flagged = ctx.timestamp;
+            ctx.db.suspicious_activity().activity_id().update(activity);
+        } else {
+            ctx.db.suspicious_activity().insert(SuspiciousActivity {
+                activity_id: 0, // Auto-incremented
+                identity,
+                reason,
+                violations,
+                times_flagged: 1,
+                first_flagged: ctx.timestamp,
+                last_flagged: ctx.timestamp,
+            });
+        }
+    }
+
+    // A full bucket behaves exactly like a missing one, so idle buckets need not be kept
+    let idle: Vec<u64> = ctx.db.rate_limit().iter()
+        .filter(|bucket| {
+            let (capacity, refill_per_second) = rate_limit_params(bucket.kind);
+            let elapsed = ctx.timestamp.duration_since(bucket.last_refill).unwrap_or_default();
+            bucket.tokens + elapsed.as_secs_f32() * refill_per_second >= capacity
+        })
+        .map(|bucket| bucket.rate_limit_id)
+        .collect();
+    for rate_limit_id in idle {
+        ctx.db.rate_limit().rate_limit_id().delete(rate_limit_id);
+    }
+
+    // A failure older than a full refill no longer holds back any bucket
+    FAILED_ATTEMPTS.lock().unwrap().retain(|(_, kind, at)| {
+        let (capacity, refill_per_second) = rate_limit_params(*kind);
+        ctx.timestamp.duration_since(*at).is_none_or(|age| age.as_secs_f32() * refill_per_second < capacity)
+    });
+
+    Ok(())
+}
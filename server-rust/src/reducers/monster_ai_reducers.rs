@@ -0,0 +1,197 @@
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use std::time::Duration;
+use crate::tables::{Entity, EntityType, MonsterAi, MonsterAiState, MonsterTemplate, MovementController};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::{PhysicsWorld, NavAgent, NavLinkKind, NavStep};
+use crate::reducers::physics_reducers::drive_movement;
+use crate::reducers::combat_reducers::is_dead;
+use crate::reducers::status_effect_reducers::is_incapacitated;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::monster::{monster, monster_ai, monster_template};
+use crate::tables::movement_controller::movement_controller;
+
+// Advance every monster's AI state machine and drive its body for this tick
+// Called from physics_step before the simulation is stepped.
+pub fn update_monster_ai(ctx: &ReducerContext, physics_world: &mut PhysicsWorld) {
+    for mut ai in ctx.db.monster_ai().iter() {
+        let Some(entity) = ctx.db.entity().entity_id().find(ai.entity_id) else {
+            continue;
+        };
+        let Some(template) = ctx.db.monster().entity_id().find(ai.entity_id)
+            .and_then(|monster| ctx.db.monster_template().template_id().find(monster.template_id)) else {
+            continue;
+        };
+        let Some(mut controller) = ctx.db.movement_controller().entity_id().find(ai.entity_id) else {
+            continue;
+        };
+
+        let before = (ai.state, ai.target_entity_id, ai.state_until);
+        let facing_before = controller.facing;
+        // Stunned and frozen monsters neither think nor move
+        let (horizontal, jump) = if is_incapacitated(ctx, ai.entity_id) {
+            (0.0, false)
+        } else {
+            think(ctx, physics_world, &mut ai, &entity, &template, &mut controller)
+        };
+
+        // The AI turns monsters through their controller, which is also where attacks read facing from
+        let moved = drive_movement(ctx, physics_world, &mut controller, horizontal, jump);
+        if moved || controller.facing != facing_before {
+            ctx.db.movement_controller().entity_id().update(controller);
+        }
+
+        // Only write the AI row when the state machine actually changed
+        if before != (ai.state, ai.target_entity_id, ai.state_until) {
+            ctx.db.monster_ai().entity_id().update(ai);
+        }
+    }
+}
+
+// Make a monster chase whoever just attacked it, regardless of its aggro range
+pub fn provoke_monster(ctx: &ReducerContext, monster_entity_id: u32, attacker_entity_id: u32) {
+    if let Some(mut ai) = ctx.db.monster_ai().entity_id().find(monster_entity_id) {
+        if ai.target_entity_id != Some(attacker_entity_id) {
+            ai.state = MonsterAiState::Chase;
+            ai.target_entity_id = Some(attacker_entity_id);
+            ctx.db.monster_ai().entity_id().update(ai);
+        }
+    }
+}
+
+// Decide this tick's movement: returns (horizontal input, jump)
+fn think(
+    ctx: &ReducerContext,
+    physics_world: &PhysicsWorld,
+    ai: &mut MonsterAi,
+    entity: &Entity,
+    template: &MonsterTemplate,
+    controller: &mut MovementController,
+) -> (f32, bool) {
+    // Aggressive monsters look for players while they are not busy
+    if matches!(ai.state, MonsterAiState::Idle | MonsterAiState::Patrol) && template.aggro_range > 0.0 {
+        if let Some(target_entity_id) = find_nearest_player(ctx, entity, template.aggro_range) {
+            ai.state = MonsterAiState::Chase;
+            ai.target_entity_id = Some(target_entity_id);
+        }
+    }
+
+    match ai.state {
+        MonsterAiState::Idle => {
+            if ctx.timestamp >= ai.state_until {
+                use spacetimedb::rand::Rng;
+                ai.state = MonsterAiState::Patrol;
+                controller.facing = if ctx.rng().gen_bool(0.5) { 1.0 } else { -1.0 };
+                ai.state_until = random_deadline(ctx, MONSTER_PATROL_MS);
+            }
+            (0.0, false)
+        }
+        MonsterAiState::Patrol => {
+            if ctx.timestamp >= ai.state_until {
+                ai.state = MonsterAiState::Idle;
+                ai.state_until = random_deadline(ctx, MONSTER_IDLE_MS);
+                return (0.0, false);
+            }
+
+            // Turn around at the edge of the wander area or of the platform
+            let outside_wander_area = (entity.position.x - ai.home.x) * controller.facing > template.wander_radius;
+            if outside_wander_area || !physics_world.has_ground_ahead(entity.entity_id, controller.facing, MONSTER_EDGE_LOOKAHEAD) {
+                controller.facing = -controller.facing;
+            }
+            (controller.facing, false)
+        }
+        MonsterAiState::Chase => {
+            let target = ai.target_entity_id
+                .and_then(|target_entity_id| ctx.db.entity().entity_id().find(target_entity_id))
+                .filter(|target| target.map_id == entity.map_id && !is_dead(ctx, target.entity_id));
+            let Some(target) = target else {
+                give_up_chase(ai);
+                return (0.0, false);
+            };
+            if (entity.position - ai.home).magnitude() > template.leash_distance {
+                give_up_chase(ai);
+                return (0.0, false);
+            }
+
+            // Target on another platform: follow the navigation graph when a route exists
+            let agent = NavAgent {
+                move_speed: controller.move_speed,
+                jump_force: controller.jump_force,
+                gravity: physics_world.gravity.y.abs(),
+                can_climb: false,
+            };
+            let feet = |position: DbVector2| (position.x, position.y - CHARACTER_HALF_HEIGHT);
+            let route = physics_world.nav_graphs.get(&entity.map_id)
+                .and_then(|graph| graph.next_step(feet(entity.position), feet(target.position), &agent));
+            if let Some(step) = route {
+                return follow_nav_step(controller, entity, &step);
+            }
+
+            let dx = target.position.x - entity.position.x;
+            if dx.abs() <= MONSTER_CHASE_STOP_DISTANCE {
+                return (0.0, false);
+            }
+            controller.facing = dx.signum();
+
+            // Only walk off a ledge when the target is below
+            let target_below = target.position.y < entity.position.y - MONSTER_CHASE_JUMP_HEIGHT;
+            if !target_below && !physics_world.has_ground_ahead(entity.entity_id, controller.facing, MONSTER_EDGE_LOOKAHEAD) {
+                return (0.0, false);
+            }
+
+            let target_above = target.position.y > entity.position.y + MONSTER_CHASE_JUMP_HEIGHT;
+            (controller.facing, target_above)
+        }
+        MonsterAiState::Return => {
+            let dx = ai.home.x - entity.position.x;
+            if dx.abs() <= MONSTER_RETURN_DISTANCE {
+                ai.state = MonsterAiState::Idle;
+                ai.state_until = random_deadline(ctx, MONSTER_IDLE_MS);
+                return (0.0, false);
+            }
+            controller.facing = dx.signum();
+            (controller.facing, false)
+        }
+    }
+}
+
+// Walk to the link's departure point, then take the link toward its landing point
+fn follow_nav_step(controller: &mut MovementController, entity: &Entity, step: &NavStep) -> (f32, bool) {
+    let to_departure = step.departure_x - entity.position.x;
+    if to_departure.abs() > NAV_DEPARTURE_TOLERANCE {
+        controller.facing = to_departure.signum();
+        return (controller.facing, false);
+    }
+
+    let to_landing = step.landing_x - entity.position.x;
+    if to_landing != 0.0 {
+        controller.facing = to_landing.signum();
+    }
+
+    match step.kind {
+        NavLinkKind::Walk | NavLinkKind::Drop => (controller.facing, false),
+        NavLinkKind::Jump => (controller.facing, true),
+        NavLinkKind::Climb => (0.0, false), // Climbing is not simulated for monsters
+    }
+}
+
+fn give_up_chase(ai: &mut MonsterAi) {
+    ai.state = MonsterAiState::Return;
+    ai.target_entity_id = None;
+}
+
+// Closest player entity on the same map within `range`
+fn find_nearest_player(ctx: &ReducerContext, entity: &Entity, range: f32) -> Option<u32> {
+    ctx.db.entity().map_cell().filter(entity.map_id)
+        .filter(|candidate| candidate.entity_type == EntityType::Player && !is_dead(ctx, candidate.entity_id))
+        .map(|candidate| (candidate.entity_id, (candidate.position - entity.position).sqr_magnitude()))
+        .filter(|(_, distance_sq)| *distance_sq <= range * range)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity_id, _)| entity_id)
+}
+
+fn random_deadline(ctx: &ReducerContext, (min_ms, max_ms): (u64, u64)) -> Timestamp {
+    use spacetimedb::rand::Rng;
+    ctx.timestamp + Duration::from_millis(ctx.rng().gen_range(min_ms..=max_ms))
+}
@@ -0,0 +1,146 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt, Timestamp};
+use std::time::Duration;
+use crate::tables::{MonsterTemplate, MonsterSpawn, MonsterSpawnTimer};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::reducers::entity_reducers::spawn_monster;
+use crate::reducers::physics_reducers::with_physics_world;
+use crate::reducers::moderation_reducers::require_scheduler;
+// Import table access traits
+use crate::tables::monster::{monster, monster_template, monster_spawn};
+use crate::tables::timers::monster_spawn_timer;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed starter monster data and schedule spawn point upkeep
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_monsters(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.monster_template().try_insert(MonsterTemplate {
+        template_id: 100100,
+        name: "Snail".into(),
+        level: 1,
+        move_speed: 1.0,
+        jump_force: 0.0,
+        aggro_range: 0.0,
+        leash_distance: 15.0,
+        wander_radius: 8.0,
+        max_hp: 15,
+        max_mp: 0,
+        attack: 5,
+        defense: 0,
+        accuracy: 10,
+        avoidability: 0,
+        exp: 3,
+        drop_table_id: Some(100100),
+    })?;
+    ctx.db.monster_template().try_insert(MonsterTemplate {
+        template_id: 1210102,
+        name: "Orange Mushroom".into(),
+        level: 8,
+        move_speed: 1.5,
+        jump_force: 6.0,
+        aggro_range: 6.0,
+        leash_distance: 20.0,
+        wander_radius: 10.0,
+        max_hp: 80,
+        max_mp: 10,
+        attack: 15,
+        defense: 5,
+        accuracy: 25,
+        avoidability: 8,
+        exp: 15,
+        drop_table_id: Some(1210102),
+    })?;
+
+    // Ground top sits at y = 0 (see init_physics)
+    for (x, template_id) in [(200.0, 100100), (500.0, 1210102), (800.0, 100100)] {
+        ctx.db.monster_spawn().try_insert(MonsterSpawn {
+            spawn_id: 0, // Auto-incremented
+            map_id: DEFAULT_MAP_ID,
+            position: DbVector2::new(x, 5.0),
+            spread: 20.0,
+            template_id,
+            max_alive: 4,
+            respawn_interval_ms: 7000,
+            next_respawn_at: Timestamp::UNIX_EPOCH,
+        })?;
+    }
+
+    ctx.db.monster_spawn_timer().try_insert(MonsterSpawnTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(MONSTER_SPAWN_CHECK_INTERVAL_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Top up spawn point populations once their respawn interval has elapsed
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn monster_spawn_tick(ctx: &ReducerContext, _timer: MonsterSpawnTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let spawns: Vec<MonsterSpawn> = ctx.db.monster_spawn().iter().collect();
+    with_physics_world(|physics_world| {
+        for spawn in spawns {
+            if ctx.timestamp < spawn.next_respawn_at {
+                continue;
+            }
+
+            let alive = ctx.db.monster().spawn_id().filter(spawn.spawn_id).count() as u32;
+            if alive >= spawn.max_alive {
+                continue;
+            }
+
+            let Some(template) = ctx.db.monster_template().template_id().find(spawn.template_id) else {
+                log::error!("Monster spawn {} references unknown template {}", spawn.spawn_id, spawn.template_id);
+                continue;
+            };
+
+            for _ in alive..spawn.max_alive {
+                spawn_at_spawn_point(ctx, physics_world, &spawn, &template);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// Start the respawn interval of the spawn point owning a monster that is about to be removed
+// Only the death that takes a full spawn point below its cap stamps the timer, so the interval
+// runs from when the population first dropped and later deaths do not postpone the wave.
+pub fn start_respawn_interval(ctx: &ReducerContext, entity_id: u32) {
+    let Some(monster) = ctx.db.monster().entity_id().find(entity_id) else {
+        return;
+    };
+    let Some(mut spawn) = ctx.db.monster_spawn().spawn_id().find(monster.spawn_id) else {
+        return;
+    };
+
+    let alive = ctx.db.monster().spawn_id().filter(spawn.spawn_id).count() as u32;
+    if alive >= spawn.max_alive {
+        spawn.next_respawn_at = ctx.timestamp + Duration::from_millis(spawn.respawn_interval_ms);
+        ctx.db.monster_spawn().spawn_id().update(spawn);
+    }
+}
+
+// Place one monster on the platform below a random point of the spawn area
+fn spawn_at_spawn_point(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    spawn: &MonsterSpawn,
+    template: &MonsterTemplate,
+) {
+    use spacetimedb::rand::Rng;
+    let offset = ctx.rng().gen_range(-spawn.spread..=spawn.spread);
+    let anchor = DbVector2::new(spawn.position.x + offset, spawn.position.y);
+
+    let Some(ground) = physics_world.find_ground_below(anchor.to_nalgebra(), MONSTER_SPAWN_MAX_DROP) else {
+        log::warn!("Monster spawn {} has no platform below ({}, {})", spawn.spawn_id, anchor.x, anchor.y);
+        return;
+    };
+
+    let position = DbVector2::new(ground.x, ground.y + CHARACTER_HALF_HEIGHT);
+    spawn_monster(ctx, physics_world, template, spawn.spawn_id, spawn.map_id, position);
+}
@@ -0,0 +1,314 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt, Timestamp};
+use std::time::Duration;
+use crate::tables::{Entity, Party, PartyInvite, PartyMember, PartyRefreshTimer};
+use crate::config::*;
+use crate::reducers::moderation_reducers::require_scheduler;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::combat::vitals;
+use crate::tables::entity::entity;
+use crate::tables::party::{party, party_member, party_invite};
+use crate::tables::player::{player, logged_out_player, player_entity};
+use crate::tables::timers::party_refresh_timer;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Schedule member status refreshes
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_parties(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.party_refresh_timer().try_insert(PartyRefreshTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(PARTY_REFRESH_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Invite an online player; the party is created when someone accepts the caller's
+// first invitation, so declined or expired invitations never leave a party of one
+#[reducer]
+pub fn invite_to_party(ctx: &ReducerContext, invitee_player_id: u32) -> Result<(), String> {
+    let inviter = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    if invitee_player_id == inviter.player_id {
+        return Err("Cannot invite yourself".into());
+    }
+    if ctx.db.player().player_id().find(invitee_player_id).is_none() {
+        return Err("That player is not online".into());
+    }
+    if party_of(ctx, invitee_player_id).is_some() {
+        return Err("That player is already in a party".into());
+    }
+
+    let party_id = match party_of(ctx, inviter.player_id) {
+        Some(party_id) => {
+            let party = ctx.db.party().party_id().find(party_id)
+                .ok_or("Party not found")?;
+            if party.leader_player_id != inviter.player_id {
+                return Err("Only the party leader can invite".into());
+            }
+            if member_count(ctx, party_id) >= MAX_PARTY_SIZE {
+                return Err(format!("Parties are limited to {} members", MAX_PARTY_SIZE));
+            }
+            party_id
+        }
+        None => 0,
+    };
+
+    let already_invited = ctx.db.party_invite().invitee_player_id().filter(invitee_player_id)
+        .any(|invite| invite.party_id == party_id && (party_id != 0 || invite.inviter_player_id == inviter.player_id));
+    if already_invited {
+        return Err("That player has already been invited".into());
+    }
+
+    ctx.db.party_invite().insert(PartyInvite {
+        invite_id: 0, // Auto-incremented
+        party_id,
+        inviter_player_id: inviter.player_id,
+        invitee_player_id,
+        created_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// Join the party of an invitation addressed to the caller
+#[reducer]
+pub fn accept_party_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let invite = own_invite(ctx, invite_id, player.player_id)?;
+
+    if party_of(ctx, player.player_id).is_some() {
+        return Err("You are already in a party".into());
+    }
+    if invite.party_id == 0 {
+        return form_party(ctx, &invite);
+    }
+    if ctx.db.party().party_id().find(invite.party_id).is_none() {
+        ctx.db.party_invite().invite_id().delete(invite_id);
+        return Err("That party no longer exists".into());
+    }
+    if member_count(ctx, invite.party_id) >= MAX_PARTY_SIZE {
+        return Err("That party is full".into());
+    }
+
+    add_member(ctx, invite.party_id, player.player_id);
+    ctx.db.party_invite().invitee_player_id().delete(player.player_id);
+
+    Ok(())
+}
+
+// Turn down an invitation addressed to the caller
+#[reducer]
+pub fn decline_party_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    own_invite(ctx, invite_id, player.player_id)?;
+
+    ctx.db.party_invite().invite_id().delete(invite_id);
+
+    Ok(())
+}
+
+// Leave the caller's party
+#[reducer]
+pub fn leave_party(ctx: &ReducerContext) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let party_id = party_of(ctx, player.player_id)
+        .ok_or("You are not in a party")?;
+
+    remove_member(ctx, party_id, player.player_id);
+
+    Ok(())
+}
+
+// Remove a member from the caller's party (leader only)
+#[reducer]
+pub fn kick_from_party(ctx: &ReducerContext, player_id: u32) -> Result<(), String> {
+    let party = led_party(ctx)?;
+    if player_id == party.leader_player_id {
+        return Err("Use leave_party to leave your own party".into());
+    }
+    if party_of(ctx, player_id) != Some(party.party_id) {
+        return Err("That player is not in your party".into());
+    }
+
+    remove_member(ctx, party.party_id, player_id);
+
+    Ok(())
+}
+
+// Hand leadership to another member (leader only)
+#[reducer]
+pub fn transfer_party_leader(ctx: &ReducerContext, player_id: u32) -> Result<(), String> {
+    let mut party = led_party(ctx)?;
+    if player_id == party.leader_player_id {
+        return Err("You already lead this party".into());
+    }
+    if party_of(ctx, player_id) != Some(party.party_id) {
+        return Err("That player is not in your party".into());
+    }
+
+    party.leader_player_id = player_id;
+    ctx.db.party().party_id().update(party);
+
+    Ok(())
+}
+
+// Update member status snapshots and drop expired invitations
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn refresh_parties(ctx: &ReducerContext, _timer: PartyRefreshTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let members: Vec<PartyMember> = ctx.db.party_member().iter().collect();
+    for member in members {
+        let snapshot = member_snapshot(ctx, member.party_id, member.player_id, member.joined_at);
+        if snapshot.online != member.online || snapshot.map_id != member.map_id || snapshot.hp != member.hp
+            || snapshot.max_hp != member.max_hp || snapshot.level != member.level
+            || snapshot.job_id != member.job_id || snapshot.name != member.name {
+            ctx.db.party_member().player_id().update(snapshot);
+        }
+    }
+
+    let invite_timeout = Duration::from_millis(PARTY_INVITE_TIMEOUT_MS);
+    let expired: Vec<u64> = ctx.db.party_invite().iter()
+        .filter(|invite| ctx.timestamp.duration_since(invite.created_at).is_some_and(|age| age >= invite_timeout))
+        .map(|invite| invite.invite_id)
+        .collect();
+    for invite_id in expired {
+        ctx.db.party_invite().invite_id().delete(invite_id);
+    }
+
+    Ok(())
+}
+
+pub fn party_of(ctx: &ReducerContext, player_id: u32) -> Option<u32> {
+    ctx.db.party_member().player_id().find(player_id).map(|member| member.party_id)
+}
+
+// Players who share EXP from a kill on `map_id`: the killer plus living party members on that map
+pub fn party_members_on_map(ctx: &ReducerContext, player_id: u32, map_id: u32) -> Vec<u32> {
+    let Some(party_id) = party_of(ctx, player_id) else {
+        return vec![player_id];
+    };
+
+    let mut sharing: Vec<u32> = ctx.db.party_member().party_id().filter(party_id)
+        .map(|member| member.player_id)
+        .filter(|&member_id| member_id != player_id)
+        .filter(|&member_id| spawned_entity(ctx, member_id).is_some_and(|entity| {
+            entity.map_id == map_id && ctx.db.vitals().entity_id().find(entity.entity_id).is_some_and(|vitals| vitals.hp > 0)
+        }))
+        .collect();
+    sharing.push(player_id);
+    sharing
+}
+
+// Accept an invitation sent before the inviter had a party: create it with both players
+fn form_party(ctx: &ReducerContext, invite: &PartyInvite) -> Result<(), String> {
+    if party_of(ctx, invite.inviter_player_id).is_some() {
+        ctx.db.party_invite().invite_id().delete(invite.invite_id);
+        return Err("That invitation is no longer valid".into());
+    }
+
+    let party = ctx.db.party().insert(Party {
+        party_id: 0, // Auto-incremented
+        leader_player_id: invite.inviter_player_id,
+        created_at: ctx.timestamp,
+    });
+    add_member(ctx, party.party_id, invite.inviter_player_id);
+    add_member(ctx, party.party_id, invite.invitee_player_id);
+    ctx.db.party_invite().invitee_player_id().delete(invite.invitee_player_id);
+
+    // The inviter's other pending invitations now lead into the new party
+    let pending: Vec<PartyInvite> = ctx.db.party_invite().party_id().filter(0u32)
+        .filter(|other| other.inviter_player_id == invite.inviter_player_id)
+        .collect();
+    for mut other in pending {
+        other.party_id = party.party_id;
+        ctx.db.party_invite().invite_id().update(other);
+    }
+
+    Ok(())
+}
+
+fn member_count(ctx: &ReducerContext, party_id: u32) -> usize {
+    ctx.db.party_member().party_id().filter(party_id).count()
+}
+
+fn add_member(ctx: &ReducerContext, party_id: u32, player_id: u32) {
+    ctx.db.party_member().insert(member_snapshot(ctx, party_id, player_id, ctx.timestamp));
+}
+
+// Remove a member; the longest-standing member takes over from a departing leader,
+// and a party left with fewer than two members is disbanded
+fn remove_member(ctx: &ReducerContext, party_id: u32, player_id: u32) {
+    ctx.db.party_member().player_id().delete(player_id);
+
+    let mut remaining: Vec<PartyMember> = ctx.db.party_member().party_id().filter(party_id).collect();
+    if remaining.len() < 2 {
+        for member in remaining {
+            ctx.db.party_member().player_id().delete(member.player_id);
+        }
+        ctx.db.party_invite().party_id().delete(party_id);
+        ctx.db.party().party_id().delete(party_id);
+        return;
+    }
+
+    if let Some(mut party) = ctx.db.party().party_id().find(party_id) {
+        if party.leader_player_id == player_id {
+            remaining.sort_by_key(|member| member.joined_at);
+            party.leader_player_id = remaining[0].player_id;
+            ctx.db.party().party_id().update(party);
+        }
+    }
+}
+
+// The party the caller leads
+fn led_party(ctx: &ReducerContext) -> Result<Party, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let party = party_of(ctx, player.player_id)
+        .and_then(|party_id| ctx.db.party().party_id().find(party_id))
+        .ok_or("You are not in a party")?;
+    if party.leader_player_id != player.player_id {
+        return Err("Only the party leader can do that".into());
+    }
+    Ok(party)
+}
+
+fn own_invite(ctx: &ReducerContext, invite_id: u64, player_id: u32) -> Result<PartyInvite, String> {
+    ctx.db.party_invite().invite_id().find(invite_id)
+        .filter(|invite| invite.invitee_player_id == player_id)
+        .ok_or("Invitation not found".into())
+}
+
+pub fn spawned_entity(ctx: &ReducerContext, player_id: u32) -> Option<Entity> {
+    ctx.db.player_entity().player_id().filter(player_id)
+        .find_map(|owned| ctx.db.entity().entity_id().find(owned.entity_id))
+}
+
+// Current status of a player as their party sees it
+fn member_snapshot(ctx: &ReducerContext, party_id: u32, player_id: u32, joined_at: Timestamp) -> PartyMember {
+    let online = ctx.db.player().player_id().find(player_id);
+    let character = ctx.db.character_stats().player_id().find(player_id);
+    let entity = spawned_entity(ctx, player_id);
+    let vitals = entity.as_ref().and_then(|entity| ctx.db.vitals().entity_id().find(entity.entity_id));
+
+    PartyMember {
+        player_id,
+        party_id,
+        name: online.as_ref().map(|player| player.name.clone())
+            .or_else(|| ctx.db.logged_out_player().player_id().find(player_id).map(|player| player.name))
+            .unwrap_or_default(),
+        level: character.as_ref().map_or(1, |character| character.level),
+        job_id: character.as_ref().map_or(BEGINNER_JOB_ID, |character| character.job_id),
+        online: online.is_some(),
+        map_id: entity.as_ref().map_or(0, |entity| entity.map_id),
+        hp: vitals.as_ref().map_or(0, |vitals| vitals.hp),
+        max_hp: vitals.as_ref().map_or(0, |vitals| vitals.max_hp),
+        joined_at,
+    }
+}
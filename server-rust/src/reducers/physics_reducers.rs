@@ -1,16 +1,25 @@
 use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
 use std::time::Duration;
 use std::sync::Mutex;
-use crate::tables::{EntityType, PhysicsBody, BodyType, PhysicsStepTimer};
+use crate::tables::{Entity, EntityType, PhysicsBody, PhysicsClock, BodyType, PhysicsStepTimer, PlayerInput, MovementController};
 use crate::types::DbVector2;
 use crate::config::*;
 use crate::physics::PhysicsWorld;
+use crate::utils::GameMath;
+use crate::reducers::projectile_reducers::{process_projectiles, projectile_profile};
+use crate::reducers::monster_ai_reducers::update_monster_ai;
+use crate::reducers::map_reducers::load_map_geometry;
+use crate::reducers::combat_reducers::{is_dead, process_contact_damage};
+use crate::reducers::status_effect_reducers::{is_incapacitated, movement_multipliers};
+use crate::reducers::moderation_reducers::require_scheduler;
 // Import table access traits
 use crate::tables::entity::entity;
-use crate::tables::physics_body::physics_body;
+use crate::tables::physics_body::{physics_body, physics_clock};
 use crate::tables::movement_controller::movement_controller;
-use crate::tables::player_input::player_input;
+use crate::tables::player_input::{player_input, input_command};
 use crate::tables::timers::physics_step_timer;
+use crate::tables::projectile::projectile;
+use crate::tables::prop::prop;
 
 // Global physics world - in production, consider using SpacetimeDB's context for storage
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/ for state management patterns
@@ -28,14 +37,14 @@ pub fn init_physics(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Erro
         let mut world_lock = PHYSICS_WORLD.lock().unwrap();
         *world_lock = Some(PhysicsWorld::new());
         
-        // Create ground plane for the world
+        // Create static colliders and navigation graphs from map geometry
         if let Some(ref mut world) = *world_lock {
-            let ground_position = rapier2d::na::Vector2::new(500.0, -10.0); // Center bottom of 1000x1000 world
-            let ground_size = rapier2d::na::Vector2::new(1000.0, 20.0);     // Wide ground platform
-            world.create_static_ground(ground_position, ground_size);
+            load_map_geometry(ctx, world);
         }
     }
 
+    ctx.db.physics_clock().try_insert(PhysicsClock { id: 0, tick: 0 })?;
+
     // Schedule 50Hz physics simulation
     ctx.db.physics_step_timer().try_insert(PhysicsStepTimer {
         scheduled_id: 0,
@@ -48,65 +57,178 @@ pub fn init_physics(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Erro
 // Main physics simulation step - runs at 50Hz
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
 #[reducer]
-pub fn physics_step(ctx: &ReducerContext, _timer: PhysicsStepTimer) {
+pub fn physics_step(ctx: &ReducerContext, _timer: PhysicsStepTimer) -> Result<(), String> {
+    // Each call consumes a queued input command per player, so clients must not be able to step the world
+    require_scheduler(ctx)?;
+
     let mut world_lock = PHYSICS_WORLD.lock().unwrap();
     
     if let Some(ref mut physics_world) = *world_lock {
-        // 1. Apply player input forces to physics bodies
-        apply_player_inputs(ctx, physics_world);
+        let Some(mut clock) = ctx.db.physics_clock().id().find(0) else {
+            return Ok(());
+        };
+
+        // 1. Apply player input and monster AI forces to physics bodies
+        apply_player_inputs(ctx, physics_world, clock.tick);
+        update_monster_ai(ctx, physics_world);
         
         // 2. Step the physics simulation (20ms = 50Hz)
-        physics_world.step();
+        let collision_events = physics_world.step();
+        clock.tick += 1;
+        ctx.db.physics_clock().id().update(clock);
         
         // 3. Sync physics results back to SpacetimeDB entities
         sync_physics_to_database(ctx, physics_world);
         
         // 4. Update ground detection for jump mechanics
         update_ground_detection(ctx, physics_world);
+
+        // 5. Resolve projectile hits and lifetimes
+        process_projectiles(ctx, physics_world, &collision_events);
+
+        // 6. Monsters damage players they touch
+        process_contact_damage(ctx, physics_world);
     }
+
+    Ok(())
+}
+
+// Run a closure against the global physics world, if it has been initialized
+// Do not call this from inside physics_step - the world is already locked there.
+pub fn with_physics_world<R>(f: impl FnOnce(&mut PhysicsWorld) -> R) -> Option<R> {
+    let mut world_lock = PHYSICS_WORLD.lock().unwrap();
+    world_lock.as_mut().map(f)
 }
 
 // Apply player input to physics forces
-fn apply_player_inputs(ctx: &ReducerContext, physics_world: &mut PhysicsWorld) {
-    for input in ctx.db.player_input().iter() {
-        // Find all movement controllers for this player
-        for controller in ctx.db.movement_controller().player_id().filter(&input.player_id) {
-            // Apply horizontal movement force
-            if input.horizontal_axis.abs() > 0.01 { // Deadzone
-                physics_world.apply_movement_force(
-                    controller.entity_id,
-                    input.horizontal_axis,
-                    controller.move_speed,
-                );
+fn apply_player_inputs(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, tick: u64) {
+    for mut input in ctx.db.player_input().iter() {
+        // Without a new command the previous state is held and no jump edge occurs
+        let jump_pressed_this_tick = consume_next_input_command(ctx, &mut input);
+
+        // Dead, stunned or frozen players still have their commands acknowledged, but cannot move
+        let (horizontal, jump) = if is_dead(ctx, input.entity_id) || is_incapacitated(ctx, input.entity_id) {
+            (0.0, false)
+        } else {
+            (input.horizontal_axis, jump_pressed_this_tick)
+        };
+
+        // Drive the movement controller of the entity this input belongs to
+        if let Some(mut controller) = ctx.db.movement_controller().entity_id().find(input.entity_id) {
+            let mut controller_changed = drive_movement(
+                ctx,
+                physics_world,
+                &mut controller,
+                horizontal,
+                jump,
+            );
+
+            // Acknowledge the input so the client can reconcile its prediction
+            // (the step that follows advances the world to tick + 1)
+            if controller.last_input_sequence != input.input_sequence {
+                controller.last_input_sequence = input.input_sequence;
+                controller.last_input_tick = tick + 1;
+                controller_changed = true;
             }
-            
-            // Apply jump force if requested and allowed
-            if input.jump_pressed && controller.can_jump {
-                let jumped = physics_world.apply_jump_force(controller.entity_id, controller.jump_force);
-                
-                if jumped {
-                    // Update controller to prevent double jumping
-                    let mut updated_controller = controller;
-                    updated_controller.can_jump = false;
-                    ctx.db.movement_controller().entity_id().update(updated_controller);
-                }
+
+            if controller_changed {
+                ctx.db.movement_controller().entity_id().update(controller);
             }
         }
     }
 }
 
+// Apply one tick of platformer movement to a controlled entity
+// Shared by player input and monster AI; returns whether the controller needs saving.
+// The controller holds base speed and jump force; active status effects scale them here.
+pub fn drive_movement(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    controller: &mut MovementController,
+    horizontal: f32,
+    jump_pressed: bool,
+) -> bool {
+    let mut changed = false;
+    let (speed_multiplier, jump_multiplier) = movement_multipliers(ctx, controller.entity_id);
+
+    // Apply horizontal movement force
+    if horizontal.abs() > 0.01 { // Deadzone
+        physics_world.apply_movement_force(controller.entity_id, horizontal, controller.move_speed * speed_multiplier);
+
+        // Turn to face the direction of travel
+        if controller.facing != horizontal.signum() {
+            controller.facing = horizontal.signum();
+            changed = true;
+        }
+    }
+
+    // Apply jump force if pressed this tick and allowed
+    if jump_pressed && controller.can_jump {
+        let jumped = physics_world.apply_jump_force(controller.entity_id, controller.jump_force * jump_multiplier);
+
+        if jumped {
+            // Update controller to prevent double jumping
+            controller.can_jump = false;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+// Pop the next queued command for a player into their input state, discarding stale ones
+// Returns whether jump was pressed on this tick (edge-triggered).
+fn consume_next_input_command(ctx: &ReducerContext, input: &mut PlayerInput) -> bool {
+    let mut commands: Vec<_> = ctx.db.input_command().player_id().filter(input.player_id).collect();
+    commands.sort_by_key(|command| command.sequence);
+
+    let max_age = Duration::from_millis(INPUT_COMMAND_MAX_AGE_MS);
+    let mut next_command = None;
+    for command in commands {
+        ctx.db.input_command().command_id().delete(command.command_id);
+
+        let already_applied = command.sequence <= input.input_sequence;
+        let too_old = ctx.timestamp.duration_since(command.received_at)
+            .is_some_and(|age| age > max_age);
+        if !already_applied && !too_old {
+            next_command = Some(command);
+            break;
+        }
+    }
+
+    let Some(command) = next_command else {
+        return false;
+    };
+
+    let jump_pressed_this_tick = command.jump_pressed || (command.jump_held && !input.jump_pressed);
+    input.horizontal_axis = command.horizontal_axis;
+    input.jump_pressed = command.jump_held;
+    input.input_sequence = command.sequence;
+    input.client_tick = command.client_tick;
+    ctx.db.player_input().player_id().update(input.clone());
+
+    jump_pressed_this_tick
+}
+
 // Sync Rapier2D physics results back to SpacetimeDB entities
 fn sync_physics_to_database(ctx: &ReducerContext, physics_world: &PhysicsWorld) {
     for mut entity in ctx.db.entity().iter() {
-        if entity.entity_type == EntityType::Player {
-            if let Some((position, velocity)) = physics_world.get_body_state(entity.entity_id) {
-                // Update entity position and velocity from physics simulation
-                entity.position = DbVector2::from_nalgebra(position);
-                entity.velocity = DbVector2::from_nalgebra(velocity);
-                
-                // Update in database - clients will receive via subscription
-                ctx.db.entity().entity_id().update(entity);
+        if let Some((position, velocity)) = physics_world.get_body_state(entity.entity_id) {
+            let position = DbVector2::from_nalgebra(position);
+            let velocity = DbVector2::from_nalgebra(velocity);
+
+            // Skip resting and fixed bodies so subscribers only receive real changes
+            if entity.position == position && entity.velocity == velocity {
+                continue;
             }
+
+            // Update entity position and velocity from physics simulation
+            entity.position = position;
+            entity.velocity = velocity;
+            (entity.cell_x, entity.cell_y) = GameMath::position_to_cell(entity.position);
+            
+            // Update in database - clients will receive via subscription
+            ctx.db.entity().entity_id().update(entity);
         }
     }
 }
@@ -128,23 +250,48 @@ fn update_ground_detection(ctx: &ReducerContext, physics_world: &PhysicsWorld) {
     }
 }
 
-// Create a physics body for a newly spawned player
-pub fn create_player_physics_body(ctx: &ReducerContext, entity_id: u32, position: DbVector2, mass: u32) {
-    let mut world_lock = PHYSICS_WORLD.lock().unwrap();
-    
-    if let Some(ref mut physics_world) = *world_lock {
-        let nalgebra_pos = position.to_nalgebra();
-        let mass_f32 = mass as f32;
-        
-        // Create physics body in Rapier2D
-        physics_world.create_player_body(entity_id, nalgebra_pos, mass_f32);
-        
-        // Create physics body record in database
-        ctx.db.physics_body().insert(PhysicsBody {
-            entity_id,
-            body_type: BodyType::Dynamic,
-            on_ground: false,
-            collision_groups: 0b0001, // Default collision group
-        });
-    }
-}
\ No newline at end of file
+// Create the Rapier2D body for a newly spawned entity of any type and record it in physics_body
+pub fn create_entity_physics_body(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, entity: &Entity) {
+    let position = entity.position.to_nalgebra();
+
+    let (body_type, collision_group) = match entity.entity_type {
+        EntityType::Player | EntityType::Monster | EntityType::Npc => {
+            physics_world.create_character_body(entity.entity_id, position, entity.mass as f32);
+            (BodyType::Dynamic, COLLISION_GROUP_CHARACTER)
+        }
+        EntityType::ItemDrop => {
+            physics_world.create_item_drop_body(entity.entity_id, position, entity.mass as f32);
+            (BodyType::Dynamic, COLLISION_GROUP_ITEM)
+        }
+        EntityType::Projectile => {
+            let Some(projectile) = ctx.db.projectile().entity_id().find(entity.entity_id) else {
+                log::error!("Projectile component missing for entity {}", entity.entity_id);
+                return;
+            };
+            let (_, radius, gravity_scale) = projectile_profile(projectile.kind);
+            physics_world.create_projectile_body(
+                entity.entity_id,
+                position,
+                entity.velocity.to_nalgebra(),
+                radius,
+                gravity_scale,
+            );
+            (BodyType::Dynamic, COLLISION_GROUP_PROJECTILE)
+        }
+        EntityType::Prop => {
+            let Some(prop) = ctx.db.prop().entity_id().find(entity.entity_id) else {
+                log::error!("Prop component missing for entity {}", entity.entity_id);
+                return;
+            };
+            physics_world.create_prop_body(entity.entity_id, position, prop.size.to_nalgebra());
+            (BodyType::Static, COLLISION_GROUP_TERRAIN)
+        }
+    };
+
+    ctx.db.physics_body().insert(PhysicsBody {
+        entity_id: entity.entity_id,
+        body_type,
+        on_ground: false,
+        collision_groups: collision_group as u16,
+    });
+}
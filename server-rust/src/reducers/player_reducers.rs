@@ -1,105 +1,195 @@
 use spacetimedb::{reducer, ReducerContext, Table};
-use crate::tables::{Entity, EntityType, MovementController, PlayerInput};
+use crate::tables::{Entity, EntityType, PlayerEntity, PlayerInput, InputCommand, RateLimitKind, SuspicionReason};
 use crate::types::DbVector2;
 use crate::config::*;
-use crate::reducers::physics_reducers::create_player_physics_body;
+use crate::physics::PhysicsWorld;
+use crate::reducers::physics_reducers::{create_entity_physics_body, with_physics_world};
+use crate::reducers::entity_reducers::{insert_entity, insert_movement_controller, insert_vitals, despawn_entity};
+use crate::reducers::combat_reducers::is_dead;
+use crate::reducers::character_reducers::{ensure_character, derive_combat_stats, derive_max_vitals, derive_movement};
+use crate::reducers::moderation_reducers::{try_consume_rate_limit, record_violation};
+use crate::reducers::friend_reducers::refresh_presence;
 // Import table access traits
-use crate::tables::player::player;
-use crate::tables::entity::entity;
-use crate::tables::movement_controller::movement_controller;
-use crate::tables::player_input::player_input;
+use crate::tables::player::{player, logged_out_player, player_entity};
+use crate::tables::player_input::{player_input, input_command};
 use crate::tables::config::config;
+use crate::tables::combat::combat_stats;
 
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
 
+// Character names are unique ignoring case, so whispers and friend requests addressed
+// by name always reach the same character.
 #[reducer]
-pub fn enter_game(ctx: &ReducerContext, name: String) {
+pub fn enter_game(ctx: &ReducerContext, name: String) -> Result<(), String> {
     log::info!("Creating player with name {}", name);
     
-    if let Some(mut player) = ctx.db.player().identity().find(&ctx.sender) {
-        let player_id = player.player_id;
-        player.name = name;
-        ctx.db.player().identity().update(player);
-        spawn_player_initial_entity(ctx, player_id);
-    } else {
-        panic!("Player not found");
+    let mut player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let player_id = player.player_id;
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Name cannot be empty".into());
+    }
+    // Checked here rather than with #[unique], because players that have not entered the game yet have no name
+    let taken = ctx.db.player().iter()
+        .map(|other| (other.player_id, other.name))
+        .chain(ctx.db.logged_out_player().iter().map(|other| (other.player_id, other.name)))
+        .any(|(other_id, other_name)| other_id != player_id && other_name.eq_ignore_ascii_case(&name));
+    if taken {
+        return Err(format!("{} is already taken", name));
     }
+    player.name = name;
+    ctx.db.player().identity().update(player);
+    with_physics_world(|physics_world| {
+        // Re-entering replaces any entity left over from a previous session
+        let existing: Vec<u32> = ctx.db.player_entity().player_id().filter(player_id)
+            .map(|owned| owned.entity_id)
+            .collect();
+        for entity_id in existing {
+            despawn_entity(ctx, physics_world, entity_id);
+        }
+        spawn_player_initial_entity(ctx, physics_world, player_id);
+    }).ok_or("Physics world not initialized")?;
+
+    Ok(())
+}
+
+// Bring a dead player back at a fresh spawn point with full HP and MP
+#[reducer]
+pub fn respawn(ctx: &ReducerContext) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    let owned: Vec<u32> = ctx.db.player_entity().player_id().filter(player.player_id)
+        .map(|owned| owned.entity_id)
+        .collect();
+    if !owned.iter().any(|&entity_id| is_dead(ctx, entity_id)) {
+        return Err("Player is not dead".into());
+    }
+
+    with_physics_world(|physics_world| {
+        for entity_id in owned {
+            despawn_entity(ctx, physics_world, entity_id);
+        }
+        spawn_player_initial_entity(ctx, physics_world, player.player_id);
+    }).ok_or("Physics world not initialized")?;
+
+    Ok(())
 }
 
 // NEW: Action-based input system for platformer controls
+// Inputs are queued as commands and consumed one per physics tick, so presses that
+// happen between ticks are not lost. `sequence` is assigned by the client so it can
+// match the server's acknowledgement (MovementController.last_input_sequence)
+// against its own prediction history.
 #[reducer]
-pub fn update_player_input(ctx: &ReducerContext, horizontal: f32, jump: bool) {
-    if let Some(player) = ctx.db.player().identity().find(&ctx.sender) {
-        // Validate and clamp horizontal input to prevent cheating
-        let clamped_horizontal = horizontal.clamp(-1.0, 1.0);
-        
-        // Update or insert player input - table will automatically sync to clients
-        if let Some(mut existing_input) = ctx.db.player_input().player_id().find(&player.player_id) {
-            existing_input.horizontal_axis = clamped_horizontal;
-            existing_input.jump_pressed = jump;
-            existing_input.input_sequence += 1; // Anti-cheat sequence increment
-            ctx.db.player_input().player_id().update(existing_input);
-        } else {
-            // First input from this player
-            ctx.db.player_input().insert(PlayerInput {
-                player_id: player.player_id,
-                horizontal_axis: clamped_horizontal,
-                jump_pressed: jump,
-                input_sequence: 1,
-            });
+pub fn update_player_input(
+    ctx: &ReducerContext,
+    horizontal: f32,
+    jump_held: bool,
+    jump_pressed: bool,
+    sequence: u32,
+    client_tick: u32,
+) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+
+    // Commands only make sense once the player has an entity to drive
+    let input = ctx.db.player_input().player_id().find(player.player_id)
+        .ok_or("No spawned entity to control")?;
+
+    // Sequence numbers must strictly increase across both applied and queued commands
+    let mut queued: Vec<InputCommand> = ctx.db.input_command().player_id().filter(player.player_id).collect();
+    let latest_sequence = queued.iter()
+        .map(|command| command.sequence)
+        .fold(input.input_sequence, u32::max);
+    if sequence <= latest_sequence {
+        record_violation(ctx, SuspicionReason::InputSequence);
+        return Err(format!("Input sequence {} is not newer than {}", sequence, latest_sequence));
+    }
+
+    // Checked after the sequence: a token taken before a rejection is rolled back with the Err anyway,
+    // so stale sequences are counted through their InputSequence violations instead
+    if !try_consume_rate_limit(ctx, RateLimitKind::Input) {
+        record_violation(ctx, SuspicionReason::InputRateLimit);
+        return Err("Input rate limit exceeded".into());
+    }
+
+    // Keep the queue bounded by dropping the oldest commands
+    if queued.len() >= INPUT_COMMAND_QUEUE_CAPACITY {
+        queued.sort_by_key(|command| command.sequence);
+        for command in &queued[..=queued.len() - INPUT_COMMAND_QUEUE_CAPACITY] {
+            ctx.db.input_command().command_id().delete(command.command_id);
         }
-    } else {
-        log::warn!("Input received from unknown player: {:?}", ctx.sender);
     }
+
+    ctx.db.input_command().insert(InputCommand {
+        command_id: 0, // Auto-incremented
+        player_id: player.player_id,
+        sequence,
+        client_tick,
+        // Validate and clamp horizontal input to prevent cheating
+        horizontal_axis: horizontal.clamp(-1.0, 1.0),
+        jump_held,
+        jump_pressed,
+        received_at: ctx.timestamp,
+    });
+
+    Ok(())
 }
 
-fn spawn_player_initial_entity(ctx: &ReducerContext, player_id: u32) -> Entity {
+fn spawn_player_initial_entity(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, player_id: u32) -> Entity {
     use spacetimedb::rand::Rng;
-    let config = ctx.db.config().id().find(&0).expect("Config not found");
+    let config = ctx.db.config().id().find(0).expect("Config not found");
     let world_size = config.world_size;
     
     // Spawn player above ground level
     let x = ctx.rng().gen_range(100.0..world_size as f32 - 100.0);
     let y = 100.0; // Start above ground
     
-    spawn_player_at(ctx, player_id, START_PLAYER_MASS, DbVector2::new(x, y))
+    spawn_player_at(ctx, physics_world, player_id, START_PLAYER_MASS, DbVector2::new(x, y))
 }
 
 fn spawn_player_at(
     ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
     player_id: u32,
     mass: u32,
     position: DbVector2,
 ) -> Entity {
     // Create entity with new physics-aware structure
-    let entity = ctx.db.entity().insert(Entity {
-        entity_id: 0, // Auto-incremented
-        position,
-        velocity: DbVector2::zero(), // Start at rest
-        mass,
-        entity_type: EntityType::Player, // NEW: Specify entity type
-    });
+    let entity = insert_entity(ctx, EntityType::Player, DEFAULT_MAP_ID, position, DbVector2::zero(), mass);
 
-    // Create movement controller for platformer mechanics (replaces Circle)
-    ctx.db.movement_controller().insert(MovementController {
+    ctx.db.player_entity().insert(PlayerEntity {
         entity_id: entity.entity_id,
         player_id,
-        move_speed: PLAYER_MOVE_SPEED,
-        jump_force: PLAYER_JUMP_FORCE,
-        can_jump: false, // Will be updated by ground detection
     });
 
+    // Movement and combat components are derived from the persistent character record
+    let character = ensure_character(ctx, player_id);
+    let (move_speed, jump_force) = derive_movement(ctx, &character);
+    let (max_hp, max_mp) = derive_max_vitals(ctx, &character);
+    insert_movement_controller(ctx, entity.entity_id, move_speed, jump_force);
+    insert_vitals(ctx, entity.entity_id, max_hp, max_mp);
+    ctx.db.combat_stats().insert(derive_combat_stats(ctx, entity.entity_id, &character));
+
     // Create physics body in Rapier2D world
-    create_player_physics_body(ctx, entity.entity_id, position, mass);
+    create_entity_physics_body(ctx, physics_world, &entity);
 
     // Initialize player input state
     ctx.db.player_input().insert(PlayerInput {
         player_id,
+        entity_id: entity.entity_id,
         horizontal_axis: 0.0,
         jump_pressed: false,
         input_sequence: 0,
+        client_tick: 0,
     });
 
+    // Friends see the new map, and the name chosen in enter_game
+    refresh_presence(ctx, player_id);
+
     log::info!("Spawned player entity {} for player {}", entity.entity_id, player_id);
     entity
-}
\ No newline at end of file
+}
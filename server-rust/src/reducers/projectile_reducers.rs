@@ -0,0 +1,166 @@
+use spacetimedb::{ReducerContext, Table};
+use rapier2d::prelude::CollisionEvent;
+use std::collections::HashSet;
+use std::time::Duration;
+use crate::tables::{DamageOutcome, EffectTarget, Entity, EntityType, Projectile, ProjectileKind};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::reducers::entity_reducers::{insert_entity, despawn_entity};
+use crate::reducers::physics_reducers::create_entity_physics_body;
+use crate::reducers::combat_reducers::{apply_damage, Hit};
+use crate::reducers::status_effect_reducers::apply_status_effect;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::projectile::projectile;
+use crate::tables::skill::skill;
+
+// Damage payload carried by a projectile
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectilePayload {
+    pub damage: u32,
+    pub multiplier: f32,
+    pub pierce: u32,              // Extra targets to pass through after the first hit
+    pub skill_id: Option<u32>,    // Skill that fired it; its target status effect is applied on hit
+}
+
+// Flight characteristics per projectile kind: (speed, collider radius, gravity scale)
+pub fn projectile_profile(kind: ProjectileKind) -> (f32, f32, f32) {
+    match kind {
+        ProjectileKind::Arrow => (ARROW_SPEED, ARROW_RADIUS, ARROW_GRAVITY_SCALE),
+        ProjectileKind::ThrowingStar => (THROWING_STAR_SPEED, THROWING_STAR_RADIUS, 0.0),
+        ProjectileKind::MagicBolt => (MAGIC_BOLT_SPEED, MAGIC_BOLT_RADIUS, 0.0),
+    }
+}
+
+// Spawn a projectile entity travelling along `direction`
+// Takes the physics world directly so it can be used both inside and outside the physics step.
+pub fn spawn_projectile(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    owner_entity_id: u32,
+    kind: ProjectileKind,
+    position: DbVector2,
+    direction: DbVector2,
+    payload: ProjectilePayload,
+) -> Entity {
+    let (speed, _, _) = projectile_profile(kind);
+    let velocity = direction.normalized() * speed;
+    // Projectiles live on the same map as whoever fired them
+    let map_id = ctx.db.entity().entity_id().find(owner_entity_id)
+        .map_or(DEFAULT_MAP_ID, |owner| owner.map_id);
+
+    let entity = insert_entity(ctx, EntityType::Projectile, map_id, position, velocity, PROJECTILE_MASS);
+
+    ctx.db.projectile().insert(Projectile {
+        entity_id: entity.entity_id,
+        kind,
+        owner_entity_id,
+        damage: payload.damage,
+        damage_multiplier: payload.multiplier,
+        skill_id: payload.skill_id,
+        pierce_remaining: payload.pierce,
+        expires_at: ctx.timestamp + Duration::from_millis(PROJECTILE_LIFETIME_MS),
+    });
+    create_entity_physics_body(ctx, physics_world, &entity);
+
+    entity
+}
+
+// Resolve projectile collisions raised by the physics step and expire old projectiles
+pub fn process_projectiles(ctx: &ReducerContext, physics_world: &mut PhysicsWorld, events: &[CollisionEvent]) {
+    let mut despawned = HashSet::new();
+
+    for event in events {
+        let CollisionEvent::Started(collider1, collider2, _) = *event else {
+            continue;
+        };
+        // Either side may belong to an entity despawned earlier this tick
+        if physics_world.collider_set.get(collider1).is_none() || physics_world.collider_set.get(collider2).is_none() {
+            continue;
+        }
+        let entity1 = physics_world.collider_entity(collider1);
+        let entity2 = physics_world.collider_entity(collider2);
+
+        // Figure out which side of the pair is the projectile
+        let (projectile, other) = match (
+            entity1.and_then(|id| ctx.db.projectile().entity_id().find(id)),
+            entity2.and_then(|id| ctx.db.projectile().entity_id().find(id)),
+        ) {
+            (Some(projectile), _) => (projectile, entity2),
+            (None, Some(projectile)) => (projectile, entity1),
+            (None, None) => continue,
+        };
+
+        if despawned.contains(&projectile.entity_id) {
+            continue;
+        }
+
+        match other {
+            // Terrain has no entity - the projectile stops here
+            None => {
+                despawn_entity(ctx, physics_world, projectile.entity_id);
+                despawned.insert(projectile.entity_id);
+            }
+            Some(target_entity_id) if target_entity_id == projectile.owner_entity_id => {}
+            Some(target_entity_id) => {
+                // Targets that cannot be hit (friendly, dead, invulnerable) are passed through
+                if !resolve_projectile_hit(ctx, physics_world, &projectile, target_entity_id) {
+                    continue;
+                }
+
+                if projectile.pierce_remaining == 0 {
+                    despawn_entity(ctx, physics_world, projectile.entity_id);
+                    despawned.insert(projectile.entity_id);
+                } else {
+                    let mut updated_projectile = projectile;
+                    updated_projectile.pierce_remaining -= 1;
+                    ctx.db.projectile().entity_id().update(updated_projectile);
+                }
+            }
+        }
+    }
+
+    // Despawn projectiles that outlived their lifetime without hitting anything
+    let expired: Vec<u32> = ctx.db.projectile().iter()
+        .filter(|projectile| projectile.expires_at <= ctx.timestamp)
+        .map(|projectile| projectile.entity_id)
+        .collect();
+    for entity_id in expired {
+        despawn_entity(ctx, physics_world, entity_id);
+    }
+}
+
+// Run a projectile strike through the damage pipeline; returns whether it counted as a hit
+fn resolve_projectile_hit(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    projectile: &Projectile,
+    target_entity_id: u32,
+) -> bool {
+    let source_position = ctx.db.entity().entity_id().find(projectile.entity_id)
+        .map_or(DbVector2::zero(), |entity| entity.position);
+
+    let Some(event) = apply_damage(ctx, physics_world, &Hit {
+        attacker_entity_id: projectile.owner_entity_id,
+        target_entity_id,
+        source_position,
+        bonus_attack: projectile.damage,
+        multiplier: projectile.damage_multiplier,
+    }) else {
+        return false;
+    };
+
+    // Skill projectiles carry the skill's effect to whoever they damage
+    let effect = projectile.skill_id
+        .and_then(|skill_id| ctx.db.skill().skill_id().find(skill_id))
+        .and_then(|skill| skill.status_effect)
+        .filter(|spec| spec.target == EffectTarget::Targets);
+    if let Some(spec) = effect {
+        if event.outcome != DamageOutcome::Miss {
+            apply_status_effect(ctx, target_entity_id, &spec, Some(projectile.owner_entity_id), projectile.skill_id);
+        }
+    }
+
+    true
+}
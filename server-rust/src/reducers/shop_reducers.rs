@@ -0,0 +1,166 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use crate::tables::{Entity, InventoryTab, ItemTemplate, Shop, ShopItem};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::reducers::character_reducers::{add_mesos, spend_mesos};
+use crate::reducers::combat_reducers::living_player_entity;
+use crate::reducers::entity_reducers::spawn_npc;
+use crate::reducers::inventory_reducers::{add_item, item_at, take_from_slot};
+use crate::reducers::physics_reducers::with_physics_world;
+// Import table access traits
+use crate::tables::entity::entity;
+use crate::tables::item::{item_template, inventory_item};
+use crate::tables::npc::npc;
+use crate::tables::shop::{shop, shop_item};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+// Every shop reducer validates before writing, and returning Err rolls back any writes already made,
+// so a failed purchase or sale can never leave items or mesos half transferred.
+
+// Seed the starter shop and spawn its NPC
+// This is called from the main init reducer in game_reducers.rs, after init_physics.
+pub fn init_shops(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    let shop_id = 1;
+    let npc_id = 1012004;
+    ctx.db.shop().try_insert(Shop {
+        shop_id,
+        npc_id,
+        name: "General Store".into(),
+        recharge_price: 1,
+    })?;
+
+    // (item, price, stock)
+    let stock = [
+        (2000000, 50, None),            // Red Potion
+        (2000001, 160, None),           // Orange Potion
+        (2000003, 200, None),           // Blue Potion
+        (2070000, 500, None),           // Subi Throwing-Stars
+        (1302000, 500, Some(5)),        // Sword
+        (1002008, 1000, Some(3)),       // Brown Skullcap
+    ];
+    for (item_id, price, stock) in stock {
+        ctx.db.shop_item().try_insert(ShopItem {
+            shop_item_id: 0, // Auto-incremented
+            shop_id,
+            item_id,
+            price,
+            stock,
+        })?;
+    }
+
+    // Ground top sits at y = 0 (see init_maps)
+    with_physics_world(|physics_world| {
+        spawn_npc(ctx, physics_world, npc_id, "Shopkeeper".into(), DEFAULT_MAP_ID, DbVector2::new(520.0, 1.0));
+    }).ok_or("Physics world not initialized")?;
+
+    Ok(())
+}
+
+// Buy `quantity` units of a shop item
+#[reducer]
+pub fn buy_item(ctx: &ReducerContext, shop_item_id: u32, quantity: u32) -> Result<(), String> {
+    let (player_id, entity_id) = living_player_entity(ctx)?;
+    let mut listing = ctx.db.shop_item().shop_item_id().find(shop_item_id)
+        .ok_or("That item is not for sale")?;
+    let shop = ctx.db.shop().shop_id().find(listing.shop_id)
+        .ok_or("Shop not found")?;
+    require_near_shop(ctx, &shop, entity_id)?;
+
+    if quantity == 0 {
+        return Err("Quantity must be positive".into());
+    }
+    if let Some(stock) = listing.stock {
+        listing.stock = Some(stock.checked_sub(quantity)
+            .ok_or(format!("Only {} left in stock", stock))?);
+    }
+    let cost = listing.price.checked_mul(quantity as u64)
+        .ok_or("Purchase is too large")?;
+
+    spend_mesos(ctx, player_id, cost)?;
+    add_item(ctx, player_id, listing.item_id, quantity)?;
+    ctx.db.shop_item().shop_item_id().update(listing);
+
+    Ok(())
+}
+
+// Sell `quantity` units from an inventory slot to a shop at the item's sell price
+#[reducer]
+pub fn sell_item(ctx: &ReducerContext, shop_id: u32, tab: InventoryTab, slot: u32, quantity: u32) -> Result<(), String> {
+    let (player_id, entity_id) = living_player_entity(ctx)?;
+    let shop = ctx.db.shop().shop_id().find(shop_id)
+        .ok_or("Shop not found")?;
+    require_near_shop(ctx, &shop, entity_id)?;
+
+    let item_id = take_from_slot(ctx, player_id, tab, slot, quantity)?;
+    let template = ctx.db.item_template().item_id().find(item_id)
+        .ok_or(format!("Unknown item {}", item_id))?;
+    let earnings = sell_price(ctx, &template).checked_mul(quantity as u64)
+        .ok_or("Sale is too large")?;
+
+    add_mesos(ctx, player_id, earnings)?;
+
+    Ok(())
+}
+
+// Refill a rechargeable stack, such as throwing stars, back to its max stack size
+#[reducer]
+pub fn recharge_item(ctx: &ReducerContext, shop_id: u32, tab: InventoryTab, slot: u32) -> Result<(), String> {
+    let (player_id, entity_id) = living_player_entity(ctx)?;
+    let shop = ctx.db.shop().shop_id().find(shop_id)
+        .ok_or("Shop not found")?;
+    require_near_shop(ctx, &shop, entity_id)?;
+    if shop.recharge_price == 0 {
+        return Err(format!("{} does not recharge items", shop.name));
+    }
+
+    let mut stack = item_at(ctx, player_id, tab, slot)
+        .ok_or("No item in that slot")?;
+    let template = ctx.db.item_template().item_id().find(stack.item_id)
+        .ok_or(format!("Unknown item {}", stack.item_id))?;
+    if !template.rechargeable {
+        return Err(format!("{} cannot be recharged", template.name));
+    }
+
+    let missing = template.max_stack.saturating_sub(stack.quantity);
+    if missing == 0 {
+        return Err(format!("{} is already full", template.name));
+    }
+    let cost = shop.recharge_price.checked_mul(missing as u64)
+        .ok_or("Recharge is too large")?;
+
+    spend_mesos(ctx, player_id, cost)?;
+    stack.quantity = template.max_stack;
+    ctx.db.inventory_item().inventory_item_id().update(stack);
+
+    Ok(())
+}
+
+// Mesos paid per unit sold
+// Recharged units cost only the recharge price, so rechargeable items never sell for more than
+// the cheapest recharge; otherwise buying one unit, recharging and selling would mint mesos.
+fn sell_price(ctx: &ReducerContext, template: &ItemTemplate) -> u64 {
+    if !template.rechargeable {
+        return template.price;
+    }
+    ctx.db.shop().iter()
+        .map(|shop| shop.recharge_price)
+        .filter(|&recharge_price| recharge_price > 0)
+        .fold(template.price, u64::min)
+}
+
+// The player must stand within SHOP_INTERACTION_RANGE of one of the shop's NPCs on the same map
+fn require_near_shop(ctx: &ReducerContext, shop: &Shop, player_entity_id: u32) -> Result<(), String> {
+    let player_entity = ctx.db.entity().entity_id().find(player_entity_id)
+        .ok_or("No spawned entity")?;
+
+    let in_range = |npc_entity: &Entity| npc_entity.map_id == player_entity.map_id
+        && (npc_entity.position - player_entity.position).magnitude() <= SHOP_INTERACTION_RANGE;
+    let near = ctx.db.npc().npc_id().filter(shop.npc_id)
+        .filter_map(|npc| ctx.db.entity().entity_id().find(npc.entity_id))
+        .any(|npc_entity| in_range(&npc_entity));
+
+    if !near {
+        return Err(format!("Too far from {}", shop.name));
+    }
+    Ok(())
+}
@@ -0,0 +1,380 @@
+use spacetimedb::{reducer, ReducerContext, Table};
+use std::time::Duration;
+use crate::tables::{CharacterSkill, EffectTarget, ProjectileKind, Skill, SkillCooldown, SkillMovement, SkillShape, StackingRule, StatusEffectKind, StatusEffectSpec};
+use crate::types::DbVector2;
+use crate::config::*;
+use crate::physics::PhysicsWorld;
+use crate::reducers::combat_reducers::{living_player_entity, melee_strike, record_attack_event, start_action, MeleeArea};
+use crate::reducers::job_reducers::job_lineage;
+use crate::reducers::physics_reducers::with_physics_world;
+use crate::reducers::projectile_reducers::{spawn_projectile, ProjectilePayload};
+use crate::reducers::status_effect_reducers::apply_status_effect;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::combat::vitals;
+use crate::tables::entity::entity;
+use crate::tables::player::player;
+use crate::tables::skill::{skill, character_skill, skill_cooldown};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Seed first and second job skills
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_skills(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    // Defaults shared by every skill; each definition below overrides what it needs
+    let base = |skill_id: u32, name: &str, job_id: u32| Skill {
+        skill_id,
+        name: name.into(),
+        job_id,
+        max_level: 20,
+        damage_multiplier: 1.0,
+        damage_multiplier_per_level: 0.0,
+        hit_count: 1,
+        max_targets: 1,
+        shape: SkillShape::None,
+        range: DbVector2::zero(),
+        range_offset: 0.0,
+        mp_cost: 0,
+        cooldown_ms: 0,
+        animation_lock_ms: SKILL_ANIMATION_LOCK_MS,
+        movement: SkillMovement::None,
+        status_effect: None,
+    };
+
+    let skills = [
+        Skill {
+            damage_multiplier: 1.6,
+            damage_multiplier_per_level: 0.05,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(1.6, 1.0),
+            range_offset: 0.8,
+            mp_cost: 6,
+            ..base(1001, "Power Strike", 100)
+        },
+        Skill {
+            damage_multiplier: 0.8,
+            damage_multiplier_per_level: 0.03,
+            max_targets: 6,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(3.0, 1.2),
+            range_offset: 1.2,
+            mp_cost: 8,
+            animation_lock_ms: 700,
+            ..base(1002, "Slash Blast", 100)
+        },
+        Skill {
+            mp_cost: 10,
+            cooldown_ms: 5000,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::AttackUp,
+                magnitude: 0.15,
+                duration_ms: 120_000,
+                stacking: StackingRule::Refresh,
+                target: EffectTarget::Caster,
+            }),
+            ..base(1003, "Rage", 100)
+        },
+        Skill {
+            damage_multiplier: 0.6,
+            damage_multiplier_per_level: 0.02,
+            max_targets: 6,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(3.0, 1.5),
+            range_offset: 0.5,
+            mp_cost: 12,
+            cooldown_ms: 8000,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::Stun,
+                magnitude: 0.0,
+                duration_ms: 1500,
+                stacking: StackingRule::Refresh,
+                target: EffectTarget::Targets,
+            }),
+            ..base(1101, "Shout", 110)
+        },
+        Skill {
+            damage_multiplier: 0.7,
+            damage_multiplier_per_level: 0.04,
+            hit_count: 2,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(4.0, 1.0),
+            range_offset: 2.2,
+            mp_cost: 8,
+            ..base(2001, "Magic Claw", 200)
+        },
+        Skill {
+            max_level: 10,
+            mp_cost: 10,
+            cooldown_ms: 500,
+            animation_lock_ms: 300,
+            movement: SkillMovement::Teleport(5.0),
+            ..base(2002, "Teleport", 200)
+        },
+        Skill {
+            damage_multiplier: 1.1,
+            damage_multiplier_per_level: 0.04,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(4.0, 1.0),
+            range_offset: 2.2,
+            mp_cost: 12,
+            cooldown_ms: 3000,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::Freeze,
+                magnitude: 0.0,
+                duration_ms: 2000,
+                stacking: StackingRule::Refresh,
+                target: EffectTarget::Targets,
+            }),
+            ..base(2101, "Cold Beam", 210)
+        },
+        Skill {
+            damage_multiplier: 0.8,
+            damage_multiplier_per_level: 0.03,
+            max_targets: 3,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(3.0, 1.2),
+            range_offset: 1.5,
+            mp_cost: 14,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::Poison,
+                magnitude: 5.0,
+                duration_ms: 6000,
+                stacking: StackingRule::Stack(3),
+                target: EffectTarget::Targets,
+            }),
+            ..base(2102, "Poison Breath", 210)
+        },
+        Skill {
+            damage_multiplier: 0.9,
+            damage_multiplier_per_level: 0.02,
+            hit_count: 2,
+            shape: SkillShape::Projectile(ProjectileKind::Arrow),
+            mp_cost: 4,
+            ..base(3001, "Double Shot", 300)
+        },
+        Skill {
+            damage_multiplier: 1.2,
+            damage_multiplier_per_level: 0.03,
+            shape: SkillShape::Projectile(ProjectileKind::Arrow),
+            mp_cost: 10,
+            cooldown_ms: 4000,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::Slow,
+                magnitude: 0.4,
+                duration_ms: 4000,
+                stacking: StackingRule::Refresh,
+                target: EffectTarget::Targets,
+            }),
+            ..base(3101, "Net Shot", 310)
+        },
+        Skill {
+            damage_multiplier: 1.0,
+            damage_multiplier_per_level: 0.03,
+            hit_count: 2,
+            shape: SkillShape::Projectile(ProjectileKind::ThrowingStar),
+            mp_cost: 6,
+            ..base(4001, "Lucky Seven", 400)
+        },
+        Skill {
+            mp_cost: 15,
+            cooldown_ms: 5000,
+            status_effect: Some(StatusEffectSpec {
+                kind: StatusEffectKind::SpeedUp,
+                magnitude: 0.3,
+                duration_ms: 60_000,
+                stacking: StackingRule::Refresh,
+                target: EffectTarget::Caster,
+            }),
+            ..base(4002, "Haste", 400)
+        },
+        Skill {
+            damage_multiplier: 1.5,
+            damage_multiplier_per_level: 0.05,
+            shape: SkillShape::Melee,
+            range: DbVector2::new(1.4, 1.0),
+            range_offset: 0.7,
+            mp_cost: 5,
+            ..base(5001, "Flash Fist", 500)
+        },
+        Skill {
+            max_level: 10,
+            mp_cost: 8,
+            cooldown_ms: 2000,
+            animation_lock_ms: 200,
+            movement: SkillMovement::Dash(12.0),
+            ..base(5002, "Dash", 500)
+        },
+    ];
+    for skill in skills {
+        ctx.db.skill().try_insert(skill)?;
+    }
+
+    Ok(())
+}
+
+// Spend one SP to learn a skill or raise its level
+#[reducer]
+pub fn learn_skill(ctx: &ReducerContext, skill_id: u32) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let mut character = ctx.db.character_stats().player_id().find(player.player_id)
+        .ok_or("Character not found")?;
+    let skill = ctx.db.skill().skill_id().find(skill_id)
+        .ok_or(format!("Unknown skill {}", skill_id))?;
+
+    if !job_lineage(ctx, character.job_id).contains(&skill.job_id) {
+        return Err(format!("{} belongs to another job", skill.name));
+    }
+    if character.sp == 0 {
+        return Err("No SP available".into());
+    }
+
+    match find_character_skill(ctx, player.player_id, skill_id) {
+        Some(learned) if learned.level >= skill.max_level => {
+            return Err(format!("{} is already at its maximum level", skill.name));
+        }
+        Some(mut learned) => {
+            learned.level += 1;
+            ctx.db.character_skill().character_skill_id().update(learned);
+        }
+        None => {
+            ctx.db.character_skill().insert(CharacterSkill {
+                character_skill_id: 0, // Auto-incremented
+                player_id: player.player_id,
+                skill_id,
+                level: 1,
+            });
+        }
+    }
+
+    character.sp -= 1;
+    ctx.db.character_stats().player_id().update(character);
+
+    Ok(())
+}
+
+// Use a learned skill in the direction the caster is facing
+// Cooldown, animation lock and MP are checked and spent before the skill executes.
+#[reducer]
+pub fn use_skill(ctx: &ReducerContext, skill_id: u32) -> Result<(), String> {
+    let (player_id, caster_id) = living_player_entity(ctx)?;
+    let skill = ctx.db.skill().skill_id().find(skill_id)
+        .ok_or(format!("Unknown skill {}", skill_id))?;
+    let level = find_character_skill(ctx, player_id, skill_id)
+        .map(|learned| learned.level)
+        .ok_or(format!("{} has not been learned", skill.name))?;
+
+    let cooldown = ctx.db.skill_cooldown().player_id().filter(player_id)
+        .find(|cooldown| cooldown.skill_id == skill_id);
+    if cooldown.as_ref().is_some_and(|cooldown| ctx.timestamp < cooldown.ready_at) {
+        return Err(format!("{} is on cooldown", skill.name));
+    }
+
+    let mut vitals = ctx.db.vitals().entity_id().find(caster_id)
+        .ok_or("Caster has no vitals")?;
+    if vitals.mp < skill.mp_cost {
+        return Err("Not enough MP".into());
+    }
+
+    let facing = start_action(ctx, caster_id, skill.animation_lock_ms)?;
+
+    vitals.mp -= skill.mp_cost;
+    ctx.db.vitals().entity_id().update(vitals);
+
+    if skill.cooldown_ms > 0 {
+        let ready_at = ctx.timestamp + Duration::from_millis(skill.cooldown_ms);
+        match cooldown {
+            Some(mut cooldown) => {
+                cooldown.ready_at = ready_at;
+                ctx.db.skill_cooldown().cooldown_id().update(cooldown);
+            }
+            None => {
+                ctx.db.skill_cooldown().insert(SkillCooldown {
+                    cooldown_id: 0, // Auto-incremented
+                    player_id,
+                    skill_id,
+                    ready_at,
+                });
+            }
+        }
+    }
+
+    let multiplier = skill.damage_multiplier + skill.damage_multiplier_per_level * (level - 1) as f32;
+    with_physics_world(|physics_world| execute_skill(ctx, physics_world, caster_id, &skill, facing, multiplier))
+        .ok_or("Physics world not initialized")?
+}
+
+// Apply a skill's movement effect, then its damage
+fn execute_skill(
+    ctx: &ReducerContext,
+    physics_world: &mut PhysicsWorld,
+    caster_id: u32,
+    skill: &Skill,
+    facing: f32,
+    multiplier: f32,
+) -> Result<(), String> {
+    // Move first so the hitbox or projectiles start from where the caster ends up
+    match skill.movement {
+        SkillMovement::None => {}
+        SkillMovement::Dash(speed) => physics_world.set_horizontal_velocity(caster_id, facing * speed),
+        SkillMovement::Teleport(distance) => {
+            physics_world.teleport_horizontal(caster_id, facing * distance)
+                .ok_or("Caster has no physics body")?;
+        }
+    }
+
+    if let Some(spec) = skill.status_effect.filter(|spec| spec.target == EffectTarget::Caster) {
+        apply_status_effect(ctx, caster_id, &spec, Some(caster_id), Some(skill.skill_id));
+    }
+
+    let caster = ctx.db.entity().entity_id().find(caster_id)
+        .ok_or("Caster has no entity")?;
+    let position = physics_world.get_body_state(caster_id)
+        .map_or(caster.position, |(position, _)| DbVector2::from_nalgebra(position));
+
+    match skill.shape {
+        SkillShape::None => {
+            record_attack_event(ctx, &caster, facing, Some(skill.skill_id), (position, DbVector2::zero()), Vec::new());
+        }
+        SkillShape::Melee => {
+            let area = MeleeArea {
+                size: skill.range,
+                offset: skill.range_offset,
+                max_targets: skill.max_targets as usize,
+                hit_count: skill.hit_count,
+            };
+            let event = melee_strike(ctx, physics_world, caster_id, facing, &area, multiplier, Some(skill.skill_id));
+
+            let target_effect = skill.status_effect.filter(|spec| spec.target == EffectTarget::Targets);
+            if let (Some(event), Some(spec)) = (event, target_effect) {
+                for target_entity_id in event.hit_entity_ids {
+                    apply_status_effect(ctx, target_entity_id, &spec, Some(caster_id), Some(skill.skill_id));
+                }
+            }
+        }
+        SkillShape::Projectile(kind) => {
+            // One projectile per hit, stacked vertically
+            let payload = ProjectilePayload {
+                damage: 0,
+                multiplier,
+                pierce: skill.max_targets.saturating_sub(1),
+                skill_id: Some(skill.skill_id),
+            };
+            for line in 0..skill.hit_count {
+                let spawn_at = DbVector2::new(
+                    position.x + facing * PROJECTILE_SPAWN_OFFSET,
+                    position.y + line as f32 * SKILL_PROJECTILE_SPACING,
+                );
+                spawn_projectile(ctx, physics_world, caster_id, kind, spawn_at, DbVector2::new(facing, 0.0), payload);
+            }
+            record_attack_event(ctx, &caster, facing, Some(skill.skill_id), (position, DbVector2::zero()), Vec::new());
+        }
+    }
+
+    Ok(())
+}
+
+fn find_character_skill(ctx: &ReducerContext, player_id: u32, skill_id: u32) -> Option<CharacterSkill> {
+    ctx.db.character_skill().player_id().filter(player_id)
+        .find(|learned| learned.skill_id == skill_id)
+}
@@ -0,0 +1,163 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{DamageEvent, DamageOutcome, StackingRule, StatusEffect, StatusEffectKind, StatusEffectSpec, StatusEffectTimer};
+use crate::config::*;
+use crate::reducers::moderation_reducers::require_scheduler;
+// Import table access traits
+use crate::tables::combat::{vitals, damage_event};
+use crate::tables::entity::entity;
+use crate::tables::status_effect::status_effect;
+use crate::tables::timers::status_effect_timer;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+
+// Schedule effect expiry and damage-over-time ticks
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_status_effects(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.status_effect_timer().try_insert(StatusEffectTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(STATUS_EFFECT_TICK_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Apply an effect to a living entity, following the spec's stacking rule
+pub fn apply_status_effect(
+    ctx: &ReducerContext,
+    entity_id: u32,
+    spec: &StatusEffectSpec,
+    source_entity_id: Option<u32>,
+    source_skill_id: Option<u32>,
+) {
+    let alive = ctx.db.vitals().entity_id().find(entity_id).is_some_and(|vitals| vitals.hp > 0);
+    if !alive {
+        return;
+    }
+
+    let expires_at = ctx.timestamp + Duration::from_millis(spec.duration_ms);
+    let existing = ctx.db.status_effect().entity_id().filter(entity_id)
+        .find(|effect| effect.kind == spec.kind && effect.stacking == spec.stacking);
+
+    match (spec.stacking, existing) {
+        (StackingRule::Refresh, Some(mut effect)) => {
+            effect.magnitude = effect.magnitude.max(spec.magnitude);
+            effect.expires_at = expires_at;
+            ctx.db.status_effect().effect_id().update(effect);
+        }
+        (StackingRule::Stack(max_stacks), Some(mut effect)) => {
+            effect.stacks = (effect.stacks + 1).min(max_stacks.max(1));
+            effect.expires_at = expires_at;
+            ctx.db.status_effect().effect_id().update(effect);
+        }
+        _ => {
+            ctx.db.status_effect().insert(StatusEffect {
+                effect_id: 0, // Auto-incremented
+                entity_id,
+                kind: spec.kind,
+                source_entity_id,
+                source_skill_id,
+                magnitude: spec.magnitude,
+                stacks: 1,
+                stacking: spec.stacking,
+                applied_at: ctx.timestamp,
+                expires_at,
+                next_tick_at: ctx.timestamp + Duration::from_millis(POISON_TICK_MS),
+            });
+        }
+    }
+}
+
+// Stunned or frozen entities cannot move, attack or use skills
+pub fn is_incapacitated(ctx: &ReducerContext, entity_id: u32) -> bool {
+    active_effects(ctx, entity_id)
+        .any(|effect| matches!(effect.kind, StatusEffectKind::Stun | StatusEffectKind::Freeze))
+}
+
+// Multipliers for movement speed and jump force from active effects: (speed, jump)
+pub fn movement_multipliers(ctx: &ReducerContext, entity_id: u32) -> (f32, f32) {
+    let mut speed = 1.0;
+    let mut jump = 1.0;
+    for effect in active_effects(ctx, entity_id) {
+        let amount = effect.magnitude * effect.stacks as f32;
+        match effect.kind {
+            StatusEffectKind::SpeedUp => speed += amount,
+            StatusEffectKind::Slow => speed -= amount,
+            StatusEffectKind::JumpUp => jump += amount,
+            StatusEffectKind::Stun | StatusEffectKind::Freeze => return (0.0, 0.0),
+            StatusEffectKind::AttackUp | StatusEffectKind::Poison => {}
+        }
+    }
+    (f32::max(speed, 0.0), f32::max(jump, 0.0))
+}
+
+// Multiplier for attack from active effects
+pub fn attack_multiplier(ctx: &ReducerContext, entity_id: u32) -> f32 {
+    1.0 + active_effects(ctx, entity_id)
+        .filter(|effect| effect.kind == StatusEffectKind::AttackUp)
+        .map(|effect| effect.magnitude * effect.stacks as f32)
+        .sum::<f32>()
+}
+
+// Expire effects and apply poison ticks
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn status_effect_tick(ctx: &ReducerContext, _timer: StatusEffectTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().iter().collect();
+
+    for mut effect in effects {
+        if effect.expires_at <= ctx.timestamp {
+            ctx.db.status_effect().effect_id().delete(effect.effect_id);
+            continue;
+        }
+
+        if effect.kind == StatusEffectKind::Poison && effect.next_tick_at <= ctx.timestamp {
+            apply_poison_tick(ctx, &effect);
+            effect.next_tick_at += Duration::from_millis(POISON_TICK_MS);
+            ctx.db.status_effect().effect_id().update(effect);
+        }
+    }
+
+    Ok(())
+}
+
+// Effects on an entity that have not expired yet
+// The tick reducer removes expired rows, but it may lag behind by up to STATUS_EFFECT_TICK_MS.
+fn active_effects(ctx: &ReducerContext, entity_id: u32) -> impl Iterator<Item = StatusEffect> + '_ {
+    ctx.db.status_effect().entity_id().filter(entity_id)
+        .filter(|effect| effect.expires_at > ctx.timestamp)
+}
+
+// Poison drains HP but always leaves the target with at least 1
+fn apply_poison_tick(ctx: &ReducerContext, effect: &StatusEffect) {
+    let Some(mut vitals) = ctx.db.vitals().entity_id().find(effect.entity_id) else {
+        return;
+    };
+    let Some(target) = ctx.db.entity().entity_id().find(effect.entity_id) else {
+        return;
+    };
+    if vitals.hp <= 1 {
+        return;
+    }
+
+    let damage = (effect.magnitude * effect.stacks as f32).round() as u32;
+    let amount = damage.min(vitals.hp - 1);
+    if amount == 0 {
+        return;
+    }
+    vitals.hp -= amount;
+    ctx.db.vitals().entity_id().update(vitals);
+
+    ctx.db.damage_event().insert(DamageEvent {
+        event_id: 0, // Auto-incremented
+        map_id: target.map_id,
+        attacker_entity_id: effect.source_entity_id.unwrap_or(effect.entity_id),
+        target_entity_id: effect.entity_id,
+        outcome: DamageOutcome::Poison,
+        amount,
+        killed: false,
+        created_at: ctx.timestamp,
+    });
+}
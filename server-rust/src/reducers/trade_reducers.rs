@@ -0,0 +1,358 @@
+use spacetimedb::{reducer, ReducerContext, Table, ScheduleAt};
+use std::time::Duration;
+use crate::tables::{Entity, InventoryTab, TradeCheckTimer, TradeLog, TradeOffer, TradeOutcome, TradeSession, TradeState, TradedItem};
+use crate::config::*;
+use crate::reducers::character_reducers::{add_mesos, spend_mesos};
+use crate::reducers::combat_reducers::living_player_entity;
+use crate::reducers::inventory_reducers::{add_item, item_at, take_from_slot};
+use crate::reducers::moderation_reducers::require_scheduler;
+// Import table access traits
+use crate::tables::character::character_stats;
+use crate::tables::entity::entity;
+use crate::tables::player::{player, player_entity};
+use crate::tables::timers::trade_check_timer;
+use crate::tables::trade::{trade_session, trade_offer, trade_log};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for reducer definitions
+//
+// Flow: invite -> accept -> offer items/mesos -> both lock -> both confirm -> exchange.
+// Changing an offer unlocks both sides, so nobody can confirm a trade that changed after they locked.
+
+// Schedule the watchdog that cancels stale or out-of-range trades
+// This is called from the main init reducer in game_reducers.rs
+pub fn init_trades(ctx: &ReducerContext) -> Result<(), Box<dyn std::error::Error>> {
+    ctx.db.trade_check_timer().try_insert(TradeCheckTimer {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(TRADE_CHECK_MS).into()),
+    })?;
+
+    Ok(())
+}
+
+// Invite a nearby player to trade
+#[reducer]
+pub fn invite_trade(ctx: &ReducerContext, partner_player_id: u32) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    if partner_player_id == player_id {
+        return Err("Cannot trade with yourself".into());
+    }
+    if ctx.db.player().player_id().find(partner_player_id).is_none() {
+        return Err("That player is not online".into());
+    }
+    if !trades_of(ctx, player_id).is_empty() {
+        return Err("You are already trading".into());
+    }
+    if !trades_of(ctx, partner_player_id).is_empty() {
+        return Err("That player is already trading".into());
+    }
+    require_traders_in_range(ctx, player_id, partner_player_id)?;
+
+    ctx.db.trade_session().insert(TradeSession {
+        trade_id: 0, // Auto-incremented
+        initiator_player_id: player_id,
+        partner_player_id,
+        state: TradeState::Invited,
+        initiator_mesos: 0,
+        partner_mesos: 0,
+        initiator_locked: false,
+        partner_locked: false,
+        initiator_confirmed: false,
+        partner_confirmed: false,
+        created_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
+
+// Accept a trade invitation
+#[reducer]
+pub fn accept_trade(ctx: &ReducerContext, trade_id: u64) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let mut trade = trade_for(ctx, trade_id, player_id)?;
+    if trade.partner_player_id != player_id {
+        return Err("Only the invited player can accept".into());
+    }
+    if trade.state != TradeState::Invited {
+        return Err("Trade was already accepted".into());
+    }
+    require_traders_in_range(ctx, trade.initiator_player_id, trade.partner_player_id)?;
+
+    trade.state = TradeState::Open;
+    ctx.db.trade_session().trade_id().update(trade);
+
+    Ok(())
+}
+
+// Offer `quantity` from an inventory slot; offering the same slot again replaces the quantity
+#[reducer]
+pub fn offer_trade_item(ctx: &ReducerContext, trade_id: u64, tab: InventoryTab, slot: u32, quantity: u32) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let trade = editable_trade(ctx, trade_id, player_id)?;
+
+    let stack = item_at(ctx, player_id, tab, slot)
+        .ok_or("No item in that slot")?;
+    if quantity == 0 || quantity > stack.quantity {
+        return Err(format!("Cannot offer {} of {}", quantity, stack.quantity));
+    }
+
+    let own_offers: Vec<TradeOffer> = ctx.db.trade_offer().trade_id().filter(trade_id)
+        .filter(|offer| offer.player_id == player_id)
+        .collect();
+    let offered = own_offers.len();
+    match own_offers.into_iter().find(|offer| offer.tab == tab && offer.slot == slot) {
+        Some(mut offer) => {
+            offer.item_id = stack.item_id;
+            offer.quantity = quantity;
+            ctx.db.trade_offer().offer_id().update(offer);
+        }
+        None => {
+            if offered >= TRADE_MAX_ITEMS {
+                return Err(format!("Cannot offer more than {} items", TRADE_MAX_ITEMS));
+            }
+            ctx.db.trade_offer().insert(TradeOffer {
+                offer_id: 0, // Auto-incremented
+                trade_id,
+                player_id,
+                tab,
+                slot,
+                item_id: stack.item_id,
+                quantity,
+            });
+        }
+    }
+
+    unlock(ctx, trade);
+
+    Ok(())
+}
+
+// Withdraw an item offer
+#[reducer]
+pub fn withdraw_trade_item(ctx: &ReducerContext, trade_id: u64, tab: InventoryTab, slot: u32) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let trade = editable_trade(ctx, trade_id, player_id)?;
+
+    let offer = ctx.db.trade_offer().trade_id().filter(trade_id)
+        .find(|offer| offer.player_id == player_id && offer.tab == tab && offer.slot == slot)
+        .ok_or("That slot is not offered")?;
+    ctx.db.trade_offer().offer_id().delete(offer.offer_id);
+
+    unlock(ctx, trade);
+
+    Ok(())
+}
+
+// Set how many mesos this side offers
+#[reducer]
+pub fn offer_trade_mesos(ctx: &ReducerContext, trade_id: u64, amount: u64) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let mut trade = editable_trade(ctx, trade_id, player_id)?;
+
+    let balance = ctx.db.character_stats().player_id().find(player_id)
+        .map_or(0, |character| character.mesos);
+    if amount > balance {
+        return Err(format!("Not enough mesos: have {}, offered {}", balance, amount));
+    }
+
+    if trade.initiator_player_id == player_id {
+        trade.initiator_mesos = amount;
+    } else {
+        trade.partner_mesos = amount;
+    }
+    unlock(ctx, trade);
+
+    Ok(())
+}
+
+// Finalise this side's offer; once both sides lock, the trade waits for confirmations
+#[reducer]
+pub fn lock_trade(ctx: &ReducerContext, trade_id: u64) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let mut trade = editable_trade(ctx, trade_id, player_id)?;
+
+    if trade.initiator_player_id == player_id {
+        trade.initiator_locked = true;
+    } else {
+        trade.partner_locked = true;
+    }
+    if trade.initiator_locked && trade.partner_locked {
+        trade.state = TradeState::Locked;
+    }
+    ctx.db.trade_session().trade_id().update(trade);
+
+    Ok(())
+}
+
+// Confirm the locked trade; the second confirmation exchanges everything in this reducer
+// Any failure returns Err, rolling back every transfer made so far.
+#[reducer]
+pub fn confirm_trade(ctx: &ReducerContext, trade_id: u64) -> Result<(), String> {
+    let (player_id, _) = living_player_entity(ctx)?;
+    let mut trade = trade_for(ctx, trade_id, player_id)?;
+    if trade.state != TradeState::Locked {
+        return Err("Both sides must lock before confirming".into());
+    }
+
+    if trade.initiator_player_id == player_id {
+        trade.initiator_confirmed = true;
+    } else {
+        trade.partner_confirmed = true;
+    }
+    if !(trade.initiator_confirmed && trade.partner_confirmed) {
+        ctx.db.trade_session().trade_id().update(trade);
+        return Ok(());
+    }
+
+    require_traders_in_range(ctx, trade.initiator_player_id, trade.partner_player_id)?;
+
+    // Take everything from both sides before giving anything, so neither side can receive without paying
+    let offers: Vec<TradeOffer> = ctx.db.trade_offer().trade_id().filter(trade_id).collect();
+    let mut initiator_items = Vec::new();
+    let mut partner_items = Vec::new();
+    for offer in &offers {
+        let item_id = take_from_slot(ctx, offer.player_id, offer.tab, offer.slot, offer.quantity)?;
+        if item_id != offer.item_id {
+            return Err("Offered items were moved; trade cannot complete".into());
+        }
+        let traded = TradedItem { item_id, quantity: offer.quantity };
+        if offer.player_id == trade.initiator_player_id {
+            initiator_items.push(traded);
+        } else {
+            partner_items.push(traded);
+        }
+    }
+    spend_mesos(ctx, trade.initiator_player_id, trade.initiator_mesos)?;
+    spend_mesos(ctx, trade.partner_player_id, trade.partner_mesos)?;
+
+    for item in &initiator_items {
+        add_item(ctx, trade.partner_player_id, item.item_id, item.quantity)?;
+    }
+    for item in &partner_items {
+        add_item(ctx, trade.initiator_player_id, item.item_id, item.quantity)?;
+    }
+    add_mesos(ctx, trade.partner_player_id, trade.initiator_mesos)?;
+    add_mesos(ctx, trade.initiator_player_id, trade.partner_mesos)?;
+
+    log::info!("Trade {} completed between players {} and {}", trade_id, trade.initiator_player_id, trade.partner_player_id);
+    close_trade(ctx, &trade, TradeOutcome::Completed);
+
+    Ok(())
+}
+
+// Cancel or decline a trade at any stage
+#[reducer]
+pub fn cancel_trade(ctx: &ReducerContext, trade_id: u64) -> Result<(), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or("Player not found")?;
+    let trade = trade_for(ctx, trade_id, player.player_id)?;
+
+    close_trade(ctx, &trade, TradeOutcome::Cancelled(format!("Cancelled by player {}", player.player_id)));
+
+    Ok(())
+}
+
+// Cancel every trade a player is part of, such as when they disconnect
+pub fn cancel_trades_of(ctx: &ReducerContext, player_id: u32, reason: &str) {
+    for trade in trades_of(ctx, player_id) {
+        close_trade(ctx, &trade, TradeOutcome::Cancelled(reason.into()));
+    }
+}
+
+// Cancel expired invitations and trades whose players left the map or walked apart
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.reducer.html for scheduled reducers
+#[reducer]
+pub fn check_trades(ctx: &ReducerContext, _timer: TradeCheckTimer) -> Result<(), String> {
+    require_scheduler(ctx)?;
+
+    let invite_timeout = Duration::from_millis(TRADE_INVITE_TIMEOUT_MS);
+    let trades: Vec<TradeSession> = ctx.db.trade_session().iter().collect();
+
+    for trade in trades {
+        let expired = trade.state == TradeState::Invited
+            && ctx.timestamp.duration_since(trade.created_at).is_some_and(|age| age >= invite_timeout);
+        if expired {
+            close_trade(ctx, &trade, TradeOutcome::Cancelled("Invitation expired".into()));
+        } else if let Err(reason) = require_traders_in_range(ctx, trade.initiator_player_id, trade.partner_player_id) {
+            close_trade(ctx, &trade, TradeOutcome::Cancelled(reason));
+        }
+    }
+
+    Ok(())
+}
+
+fn trades_of(ctx: &ReducerContext, player_id: u32) -> Vec<TradeSession> {
+    ctx.db.trade_session().initiator_player_id().filter(player_id)
+        .chain(ctx.db.trade_session().partner_player_id().filter(player_id))
+        .collect()
+}
+
+// The trade, if the player is part of it
+fn trade_for(ctx: &ReducerContext, trade_id: u64, player_id: u32) -> Result<TradeSession, String> {
+    ctx.db.trade_session().trade_id().find(trade_id)
+        .filter(|trade| trade.initiator_player_id == player_id || trade.partner_player_id == player_id)
+        .ok_or("Trade not found".into())
+}
+
+// The trade, if it is open and this player's side is not locked yet
+fn editable_trade(ctx: &ReducerContext, trade_id: u64, player_id: u32) -> Result<TradeSession, String> {
+    let trade = trade_for(ctx, trade_id, player_id)?;
+    let locked = if trade.initiator_player_id == player_id { trade.initiator_locked } else { trade.partner_locked };
+    if trade.state != TradeState::Open || locked {
+        return Err("Your offer is locked".into());
+    }
+    Ok(trade)
+}
+
+// Save an offer change, clearing both locks so each side must review it again
+fn unlock(ctx: &ReducerContext, mut trade: TradeSession) {
+    trade.initiator_locked = false;
+    trade.partner_locked = false;
+    ctx.db.trade_session().trade_id().update(trade);
+}
+
+// Both players must be spawned on the same map within TRADE_RANGE of each other
+fn require_traders_in_range(ctx: &ReducerContext, first_player_id: u32, second_player_id: u32) -> Result<(), String> {
+    let spawned = |player_id: u32| -> Option<Entity> {
+        ctx.db.player_entity().player_id().filter(player_id)
+            .find_map(|owned| ctx.db.entity().entity_id().find(owned.entity_id))
+    };
+    let first = spawned(first_player_id).ok_or("Trader left the world")?;
+    let second = spawned(second_player_id).ok_or("Trader left the world")?;
+
+    if first.map_id != second.map_id {
+        return Err("Traders are on different maps".into());
+    }
+    if (first.position - second.position).magnitude() > TRADE_RANGE {
+        return Err("Traders are too far apart".into());
+    }
+    Ok(())
+}
+
+// Log the trade with its offers, then delete it
+fn close_trade(ctx: &ReducerContext, trade: &TradeSession, outcome: TradeOutcome) {
+    let offers: Vec<TradeOffer> = ctx.db.trade_offer().trade_id().filter(trade.trade_id).collect();
+    let items_of = |player_id: u32| -> Vec<TradedItem> {
+        offers.iter()
+            .filter(|offer| offer.player_id == player_id)
+            .map(|offer| TradedItem { item_id: offer.item_id, quantity: offer.quantity })
+            .collect()
+    };
+
+    ctx.db.trade_log().insert(TradeLog {
+        log_id: 0, // Auto-incremented
+        trade_id: trade.trade_id,
+        initiator_player_id: trade.initiator_player_id,
+        partner_player_id: trade.partner_player_id,
+        initiator_items: items_of(trade.initiator_player_id),
+        partner_items: items_of(trade.partner_player_id),
+        initiator_mesos: trade.initiator_mesos,
+        partner_mesos: trade.partner_mesos,
+        outcome,
+        logged_at: ctx.timestamp,
+    });
+
+    for offer in offers {
+        ctx.db.trade_offer().offer_id().delete(offer.offer_id);
+    }
+    ctx.db.trade_session().trade_id().delete(trade.trade_id);
+}
@@ -0,0 +1,9 @@
+use spacetimedb::{table, Identity};
+
+// Identities allowed to call administrative reducers (seeded with the publisher at init)
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = admin)]
+pub struct Admin {
+    #[primary_key]
+    pub identity: Identity,
+}
@@ -0,0 +1,33 @@
+use spacetimedb::{table, SpacetimeType};
+
+// Persistent character progression, keyed by player so it survives logouts and respawns
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = character_stats, public)]
+pub struct CharacterStats {
+    #[primary_key]
+    pub player_id: u32,
+    pub level: u32,
+    pub job_id: u32,
+    pub exp: u64,                   // Progress toward the next level
+    pub max_hp: u32,
+    pub max_mp: u32,
+    pub str: u32,
+    pub dex: u32,
+    pub int: u32,
+    pub luk: u32,
+    pub ap: u32,                    // Unspent ability points
+    pub sp: u32,                    // Unspent skill points
+    pub mesos: u64,
+    pub speed: u32,                 // Movement speed in percent of PLAYER_MOVE_SPEED
+    pub jump: u32,                  // Jump force in percent of PLAYER_JUMP_FORCE
+}
+
+// Stats that ability points can be spent on
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum StatKind {
+    Str,
+    Dex,
+    Int,
+    Luk,
+}
@@ -0,0 +1,46 @@
+use spacetimedb::{table, Identity, SpacetimeType, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ChatChannel {
+    Map,                            // Everyone on the sender's map
+    Whisper,                        // One player, addressed by character name
+    Party,
+    Guild,
+    World,                          // Megaphone: everyone online, more strictly rate limited
+}
+
+// Recent chat messages, deleted after CHAT_RETENTION_MS
+// Scope columns are 0 when they do not apply. Private because whispers, party and guild chat
+// must only reach their audience; clients read messages through the my_chat_messages view.
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = chat_message)]
+pub struct ChatMessage {
+    #[primary_key]
+    #[auto_inc]
+    pub message_id: u64,
+    pub channel: ChatChannel,
+    #[index(btree)]
+    pub sender_player_id: u32,
+    pub sender_name: String,
+    #[index(btree)]
+    pub map_id: u32,                // Map channel
+    #[index(btree)]
+    pub recipient_player_id: u32,   // Whisper channel
+    #[index(btree)]
+    pub party_id: u32,              // Party channel
+    #[index(btree)]
+    pub guild_id: u32,              // Guild channel
+    pub text: String,
+    pub sent_at: Timestamp,
+}
+
+// Players barred from chatting until muted_until
+#[table(name = chat_mute, public)]
+pub struct ChatMute {
+    #[primary_key]
+    pub player_id: u32,
+    pub muted_until: Timestamp,
+    pub reason: String,
+    pub muted_by: Identity,
+}
@@ -0,0 +1,81 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+use crate::types::DbVector2;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+// HP/MP component for anything that can take damage
+#[table(name = vitals, public)]
+pub struct Vitals {
+    #[primary_key]
+    pub entity_id: u32,
+    pub hp: u32,
+    pub max_hp: u32,
+    pub mp: u32,
+    pub max_mp: u32,
+    pub invulnerable_until: Timestamp,  // Hits are ignored until this time (post-hit i-frames)
+}
+
+// Offensive and defensive numbers used by the damage pipeline
+#[table(name = combat_stats, public)]
+pub struct CombatStats {
+    #[primary_key]
+    pub entity_id: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub accuracy: u32,
+    pub avoidability: u32,
+    pub critical_rate: f32,         // Chance per hit, 0..1
+    pub critical_damage: f32,       // Damage multiplier on a critical hit
+}
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum DamageOutcome {
+    Hit,
+    Critical,
+    Miss,
+    Poison,     // Damage over time from a status effect
+}
+
+// When an entity may next attack or use a skill (basic attack cooldown / skill animation lock)
+#[table(name = attack_cooldown, public)]
+pub struct AttackCooldown {
+    #[primary_key]
+    pub entity_id: u32,
+    pub ready_at: Timestamp,
+}
+
+// An attack swing, published so clients can animate it
+// The server alone decides what it hit; the resulting damage is in damage_event.
+#[table(name = attack_event, public)]
+pub struct AttackEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub event_id: u64,
+    #[index(btree)]
+    pub map_id: u32,
+    pub attacker_entity_id: u32,
+    pub skill_id: Option<u32>,      // None for a basic attack
+    pub facing: f32,
+    pub hitbox_center: DbVector2,
+    pub hitbox_size: DbVector2,
+    pub hit_entity_ids: Vec<u32>,   // Targets that took damage
+    pub created_at: Timestamp,
+}
+
+// One resolved hit, published so clients can show damage numbers
+// Rows are short-lived; cleanup_combat_events removes them after COMBAT_EVENT_RETENTION_MS.
+#[table(name = damage_event, public)]
+pub struct DamageEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub event_id: u64,
+    #[index(btree)]
+    pub map_id: u32,
+    pub attacker_entity_id: u32,
+    #[index(btree)]
+    pub target_entity_id: u32,
+    pub outcome: DamageOutcome,
+    pub amount: u32,                // 0 on a miss
+    pub killed: bool,
+    pub created_at: Timestamp,
+}
@@ -4,12 +4,19 @@ use crate::types::DbVector2;
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
 #[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
 pub enum EntityType {
-    Player,
-    // Future: NPCs, Items, etc.
+    Player,     // Component: player_entity
+    Monster,    // Component: monster
+    Npc,        // Component: npc
+    ItemDrop,   // Component: item_drop
+    Projectile, // Component: projectile
+    Prop,       // Component: prop
 }
 
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
-#[table(name = entity, public)]
+// The (map_id, cell_x, cell_y) index backs area-of-interest subscriptions, e.g.
+// SELECT * FROM entity WHERE map_id = 1 AND cell_x >= 3 AND cell_x <= 5 AND cell_y >= -1 AND cell_y <= 1
+// Per-map lookups use its map_id prefix, so map_id needs no index of its own.
+#[table(name = entity, public, index(name = map_cell, btree(columns = [map_id, cell_x, cell_y])))]
 pub struct Entity {
     #[primary_key]
     #[auto_inc]
@@ -18,4 +25,7 @@ pub struct Entity {
     pub velocity: DbVector2,      // NEW: Physics velocity for Rapier2D sync
     pub mass: u32,
     pub entity_type: EntityType,  // NEW: Type classification for MMORPG extensibility
+    pub map_id: u32,              // Map the entity currently lives on
+    pub cell_x: i32,              // Area-of-interest grid cell, maintained by the server from position
+    pub cell_y: i32,
 }
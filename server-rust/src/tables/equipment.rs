@@ -0,0 +1,60 @@
+use spacetimedb::{table, SpacetimeType};
+
+// Body slot an equip is worn in
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum EquipSlot {
+    Weapon,
+    Hat,
+    Top,
+    Bottom,
+    Shoes,
+    Gloves,
+    Cape,
+    Accessory,
+}
+
+// Flat stat bonuses granted while an equip is worn
+#[derive(SpacetimeType, Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatBonus {
+    pub str: u32,
+    pub dex: u32,
+    pub int: u32,
+    pub luk: u32,
+    pub max_hp: u32,
+    pub max_mp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub accuracy: u32,
+    pub avoidability: u32,
+    pub speed: u32,                 // Percentage points added to the character's speed
+    pub jump: u32,                  // Percentage points added to the character's jump
+}
+
+// Equip-specific data for item templates in the Equip tab
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = equip_template, public)]
+pub struct EquipTemplate {
+    #[primary_key]
+    pub item_id: u32,
+    pub slot: EquipSlot,
+    pub required_level: u32,
+    pub required_job_id: Option<u32>,   // Wearable by this job and every job advanced from it
+    pub required_str: u32,
+    pub required_dex: u32,
+    pub required_int: u32,
+    pub required_luk: u32,
+    pub bonus: StatBonus,
+}
+
+// Items a character is wearing; public so clients can render other players' appearance
+#[table(name = equipment, public)]
+pub struct Equipment {
+    #[primary_key]
+    #[auto_inc]
+    pub equipment_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub slot: EquipSlot,
+    pub item_id: u32,
+}
@@ -0,0 +1,33 @@
+use spacetimedb::{table, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+
+// One direction of a friendship: `friend_player_id` as it appears on `player_id`'s buddy list
+// Presence columns mirror whether the friend is in `player` or `logged_out_player`; pushed by refresh_presence.
+#[table(name = friend)]
+pub struct Friend {
+    #[primary_key]
+    #[auto_inc]
+    pub friendship_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    #[index(btree)]
+    pub friend_player_id: u32,
+    pub name: String,
+    pub online: bool,
+    pub map_id: u32,                // 0 while offline or not spawned
+    pub since: Timestamp,
+}
+
+// Pending request; kept until answered so offline characters can be asked too
+#[table(name = friend_request)]
+pub struct FriendRequest {
+    #[primary_key]
+    #[auto_inc]
+    pub request_id: u64,
+    #[index(btree)]
+    pub requester_player_id: u32,
+    #[index(btree)]
+    pub target_player_id: u32,
+    pub created_at: Timestamp,
+}
@@ -0,0 +1,63 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = guild, public)]
+pub struct Guild {
+    #[primary_key]
+    #[auto_inc]
+    pub guild_id: u32,
+    #[unique]
+    pub name: String,
+    pub master_player_id: u32,
+    pub notice: String,
+    pub created_at: Timestamp,
+}
+
+// What members of a rank may do
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, Default, PartialEq)]
+pub struct GuildPermissions {
+    pub invite: bool,
+    pub kick: bool,
+    pub promote: bool,              // Change the rank of members below their own rank
+    pub edit_notice: bool,
+}
+
+// A guild's ranks; rank 1 is the master and higher numbers rank lower
+#[table(name = guild_rank, public)]
+pub struct GuildRank {
+    #[primary_key]
+    #[auto_inc]
+    pub rank_id: u64,
+    #[index(btree)]
+    pub guild_id: u32,
+    pub rank: u32,
+    pub title: String,
+    pub permissions: GuildPermissions,
+}
+
+// Guild membership, keyed by player so it persists while members are logged out
+#[table(name = guild_member, public)]
+pub struct GuildMember {
+    #[primary_key]
+    pub player_id: u32,
+    #[index(btree)]
+    pub guild_id: u32,
+    pub rank: u32,
+    pub name: String,               // Character name when they joined or last changed rank
+    pub joined_at: Timestamp,
+}
+
+// Pending invitation, ignored after GUILD_INVITE_TIMEOUT_MS
+#[table(name = guild_invite, public)]
+pub struct GuildInvite {
+    #[primary_key]
+    #[auto_inc]
+    pub invite_id: u64,
+    #[index(btree)]
+    pub guild_id: u32,
+    pub inviter_player_id: u32,
+    #[index(btree)]
+    pub invitee_player_id: u32,
+    pub created_at: Timestamp,
+}
@@ -0,0 +1,39 @@
+use spacetimedb::{table, SpacetimeType};
+
+// Inventory tab an item is stored under
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum InventoryTab {
+    Equip,
+    Use,
+    Setup,
+    Etc,
+}
+
+// Data-driven item definitions
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = item_template, public)]
+pub struct ItemTemplate {
+    #[primary_key]
+    pub item_id: u32,
+    pub name: String,
+    pub tab: InventoryTab,
+    pub max_stack: u32,             // 1 for items that never stack, such as equipment
+    pub price: u64,                 // Mesos per unit when sold to a shop, capped at the recharge price if rechargeable
+    pub rechargeable: bool,         // Shops can refill the stack back to max_stack, such as throwing stars
+}
+
+// One stack of items in a character's inventory
+// Private so characters cannot read each other's inventories; clients subscribe to the my_inventory view.
+#[table(name = inventory_item)]
+pub struct InventoryItem {
+    #[primary_key]
+    #[auto_inc]
+    pub inventory_item_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub tab: InventoryTab,
+    pub slot: u32,                  // 0-based position within the tab
+    pub item_id: u32,
+    pub quantity: u32,
+}
@@ -0,0 +1,13 @@
+use spacetimedb::{table, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = item_drop, public)]
+pub struct ItemDrop {
+    #[primary_key]
+    pub entity_id: u32,
+    pub item_id: u32,               // Item lying on the ground, or MESO_ITEM_ID for mesos
+    pub quantity: u32,              // Meso amount for meso drops
+    pub owner_player_id: Option<u32>,   // Only this player may pick it up until exclusive_until
+    pub exclusive_until: Timestamp,
+    pub expires_at: Timestamp,      // Despawned after this
+}
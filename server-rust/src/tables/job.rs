@@ -0,0 +1,24 @@
+use spacetimedb::table;
+use crate::tables::StatKind;
+
+// Data-driven job tree: each job names the job it advances from
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = job, public)]
+pub struct Job {
+    #[primary_key]
+    pub job_id: u32,
+    pub name: String,
+    pub parent_job_id: Option<u32>,     // Job a character must have to advance into this one; None for Beginner
+    pub advancement: u32,               // 0 = Beginner, 1 = first job, 2 = second job, ...
+    pub required_level: u32,
+    pub required_stat: StatKind,
+    pub required_stat_value: u32,
+    pub required_quest_id: Option<u32>, // Quest that must be completed before advancing
+    pub primary_stat: StatKind,         // Drives attack
+    pub secondary_stat: StatKind,
+    pub hp_per_level: u32,              // Max HP/MP growth on each level up while in this job
+    pub mp_per_level: u32,
+    pub hp_bonus: u32,                  // One-time max HP/MP and SP granted on advancing into this job
+    pub mp_bonus: u32,
+    pub sp_bonus: u32,
+}
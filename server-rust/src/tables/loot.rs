@@ -0,0 +1,36 @@
+use spacetimedb::{table, SpacetimeType};
+
+// What a drop table entry produces
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum DropKind {
+    Item(u32),                      // Item id; quantity is the stack size
+    Mesos,                          // Quantity is the meso amount
+    Table(u32),                     // Nested drop table; quantity is how many times it is rolled
+    Nothing,                        // Weighted blank, so a weighted pick can come up empty
+}
+
+// How a drop table entry is selected
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum DropRule {
+    Guaranteed,                     // Always drops
+    Chance(f32),                    // Rolled independently, 0..=1
+    Weighted(u32),                  // Exactly one weighted entry per table roll, picked by weight
+}
+
+// One line of a drop table; a table is every entry sharing a table_id
+// Private so clients cannot read drop rates.
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = drop_entry)]
+#[derive(Clone)]
+pub struct DropEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub entry_id: u32,
+    #[index(btree)]
+    pub table_id: u32,
+    pub kind: DropKind,
+    pub rule: DropRule,
+    pub min_quantity: u32,
+    pub max_quantity: u32,
+}
@@ -6,6 +6,29 @@ pub mod timers;
 pub mod physics_body;
 pub mod player_input;
 pub mod movement_controller;
+pub mod projectile;
+pub mod monster;
+pub mod npc;
+pub mod item_drop;
+pub mod prop;
+pub mod platform;
+pub mod admin;
+pub mod moderation;
+pub mod combat;
+pub mod character;
+pub mod job;
+pub mod quest;
+pub mod skill;
+pub mod status_effect;
+pub mod item;
+pub mod equipment;
+pub mod loot;
+pub mod shop;
+pub mod trade;
+pub mod chat;
+pub mod party;
+pub mod guild;
+pub mod friend;
 
 pub use player::*;
 pub use entity::*;
@@ -14,4 +37,27 @@ pub use timers::*;
 // NEW: Physics and movement exports
 pub use physics_body::*;
 pub use player_input::*;
-pub use movement_controller::*;
\ No newline at end of file
+pub use movement_controller::*;
+pub use projectile::*;
+pub use monster::*;
+pub use npc::*;
+pub use item_drop::*;
+pub use prop::*;
+pub use platform::*;
+pub use admin::*;
+pub use moderation::*;
+pub use combat::*;
+pub use character::*;
+pub use job::*;
+pub use quest::*;
+pub use skill::*;
+pub use status_effect::*;
+pub use item::*;
+pub use equipment::*;
+pub use loot::*;
+pub use shop::*;
+pub use trade::*;
+pub use chat::*;
+pub use party::*;
+pub use guild::*;
+pub use friend::*;
\ No newline at end of file
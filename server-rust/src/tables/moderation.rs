@@ -0,0 +1,47 @@
+use spacetimedb::{table, Identity, SpacetimeType, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RateLimitKind {
+    Input,
+    Chat,
+    Megaphone,
+    FriendRequest,
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuspicionReason {
+    InputRateLimit,     // Sent inputs faster than the token bucket allows
+    InputSequence,      // Sent a non-monotonic input sequence number
+    ChatRateLimit,      // Sent chat messages faster than the token bucket allows
+    FriendRequestRateLimit, // Sent friend requests faster than the token bucket allows
+}
+
+// Per-identity token bucket state
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = rate_limit)]
+pub struct RateLimit {
+    #[primary_key]
+    #[auto_inc]
+    pub rate_limit_id: u64,
+    #[index(btree)]
+    pub identity: Identity,
+    pub kind: RateLimitKind,
+    pub tokens: f32,                // Remaining tokens as of last_refill
+    pub last_refill: Timestamp,
+}
+
+// Identities that repeatedly violated input rules within a flush window
+#[table(name = suspicious_activity)]
+pub struct SuspiciousActivity {
+    #[primary_key]
+    #[auto_inc]
+    pub activity_id: u64,
+    #[index(btree)]
+    pub identity: Identity,
+    pub reason: SuspicionReason,
+    pub violations: u32,            // Total violations recorded while flagged
+    pub times_flagged: u32,         // Number of windows in which the threshold was exceeded
+    pub first_flagged: Timestamp,
+    pub last_flagged: Timestamp,
+}
@@ -0,0 +1,72 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+use crate::types::DbVector2;
+
+// Data-driven monster definitions
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = monster_template, public)]
+pub struct MonsterTemplate {
+    #[primary_key]
+    pub template_id: u32,
+    pub name: String,
+    pub level: u32,
+    pub move_speed: f32,
+    pub jump_force: f32,
+    pub aggro_range: f32,           // Players within this range are chased; 0 = only aggro when hit
+    pub leash_distance: f32,        // Give up the chase this far from home
+    pub wander_radius: f32,         // Patrol stays within this distance of home
+    pub max_hp: u32,
+    pub max_mp: u32,
+    pub attack: u32,                // Also used for touch damage against players
+    pub defense: u32,
+    pub accuracy: u32,
+    pub avoidability: u32,
+    pub exp: u32,                   // Awarded to the player who lands the killing blow
+    pub drop_table_id: Option<u32>, // Rolled once on death
+}
+
+#[table(name = monster, public)]
+pub struct Monster {
+    #[primary_key]
+    pub entity_id: u32,
+    #[index(btree)]
+    pub template_id: u32,           // Which kind of monster this is
+    #[index(btree)]
+    pub spawn_id: u32,              // Spawn point that owns this monster, 0 if spawned directly
+}
+
+// Where monsters appear on a map and how quickly they come back
+#[table(name = monster_spawn, public)]
+pub struct MonsterSpawn {
+    #[primary_key]
+    #[auto_inc]
+    pub spawn_id: u32,
+    #[index(btree)]
+    pub map_id: u32,
+    pub position: DbVector2,        // Spawn anchor; monsters are dropped onto the platform below it
+    pub spread: f32,                // Horizontal distance either side of the anchor to scatter spawns
+    pub template_id: u32,
+    pub max_alive: u32,             // Population cap for this spawn point
+    pub respawn_interval_ms: u64,   // Delay between the population dropping below max_alive and the next wave
+    pub next_respawn_at: Timestamp, // Stamped when a death first takes the population below max_alive
+}
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum MonsterAiState {
+    Idle,       // Standing still until state_until
+    Patrol,     // Wandering around home, turning at edges
+    Chase,      // Following target_entity_id
+    Return,     // Walking back home after losing the target
+}
+
+// AI state machine component for monsters, advanced every physics tick
+// Facing lives on the monster's MovementController, like every other walking entity.
+#[table(name = monster_ai, public)]
+pub struct MonsterAi {
+    #[primary_key]
+    pub entity_id: u32,
+    pub state: MonsterAiState,
+    pub home: DbVector2,            // Where the monster spawned; patrol and leash are measured from here
+    pub target_entity_id: Option<u32>,
+    pub state_until: Timestamp,     // When Idle/Patrol should switch
+}
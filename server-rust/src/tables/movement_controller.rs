@@ -5,9 +5,10 @@ use spacetimedb::table;
 pub struct MovementController {
     #[primary_key]
     pub entity_id: u32,
-    #[index(btree)]
-    pub player_id: u32,            // Links to Player table for ownership
     pub move_speed: f32,            // Horizontal movement speed (platformer)
     pub jump_force: f32,            // Jump impulse strength
     pub can_jump: bool,             // Jump availability state (ground check dependent)
+    pub facing: f32,                // -1.0 left, 1.0 right; follows the last horizontal input
+    pub last_input_sequence: u32,   // Latest client input sequence applied to this entity
+    pub last_input_tick: u64,       // Physics tick on which that input was applied
 }
\ No newline at end of file
@@ -0,0 +1,11 @@
+use spacetimedb::table;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = npc, public)]
+pub struct Npc {
+    #[primary_key]
+    pub entity_id: u32,
+    #[index(btree)]
+    pub npc_id: u32,                // Stable NPC identifier (shops, quests and dialogue key off this)
+    pub name: String,
+}
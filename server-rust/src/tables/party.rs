@@ -0,0 +1,43 @@
+use spacetimedb::{table, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = party, public)]
+pub struct Party {
+    #[primary_key]
+    #[auto_inc]
+    pub party_id: u32,
+    pub leader_player_id: u32,
+    pub created_at: Timestamp,
+}
+
+// Party membership plus a status snapshot members show each other
+// Keyed by player so membership survives disconnects; refreshed by refresh_parties.
+#[table(name = party_member, public)]
+pub struct PartyMember {
+    #[primary_key]
+    pub player_id: u32,
+    #[index(btree)]
+    pub party_id: u32,
+    pub name: String,
+    pub level: u32,
+    pub job_id: u32,
+    pub online: bool,
+    pub map_id: u32,                // 0 while offline or not spawned
+    pub hp: u32,
+    pub max_hp: u32,
+    pub joined_at: Timestamp,
+}
+
+// Pending invitation, dropped after PARTY_INVITE_TIMEOUT_MS
+#[table(name = party_invite, public)]
+pub struct PartyInvite {
+    #[primary_key]
+    #[auto_inc]
+    pub invite_id: u64,
+    #[index(btree)]
+    pub party_id: u32,              // 0 until accepted if the inviter had no party yet
+    pub inviter_player_id: u32,
+    #[index(btree)]
+    pub invitee_player_id: u32,
+    pub created_at: Timestamp,
+}
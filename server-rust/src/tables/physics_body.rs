@@ -17,4 +17,14 @@ pub struct PhysicsBody {
     pub body_type: BodyType,        // Type of physics body in Rapier2D
     pub on_ground: bool,            // Ground detection for jumping mechanics
     pub collision_groups: u16,      // Rapier2D collision filtering bitmask
-}
\ No newline at end of file
+}
+
+// Single-row simulation step counter (id 0)
+// Private so the 50Hz increment is not sent to subscribers; persisted so acknowledged input
+// ticks keep increasing across restarts.
+#[table(name = physics_clock)]
+pub struct PhysicsClock {
+    #[primary_key]
+    pub id: u32,
+    pub tick: u64,
+}
@@ -0,0 +1,23 @@
+use spacetimedb::{table, SpacetimeType};
+use crate::types::DbVector2;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum PlatformKind {
+    Solid,      // Collidable terrain you can stand on
+    Ladder,     // Climbable, no collider; connects the platforms it spans
+}
+
+// Static map geometry; physics colliders and navigation graphs are built from these rows
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = platform, public)]
+pub struct Platform {
+    #[primary_key]
+    #[auto_inc]
+    pub platform_id: u32,
+    #[index(btree)]
+    pub map_id: u32,
+    pub kind: PlatformKind,
+    pub position: DbVector2,        // Center of the box
+    pub size: DbVector2,            // Full width and height
+}
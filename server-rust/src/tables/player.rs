@@ -19,4 +19,13 @@ pub struct Player {
     #[auto_inc]
     pub player_id: u32,
     pub name: String,
-}
\ No newline at end of file
+}
+
+// Links a player-controlled entity to the player that owns it
+#[table(name = player_entity, public)]
+pub struct PlayerEntity {
+    #[primary_key]
+    pub entity_id: u32,
+    #[index(btree)]
+    pub player_id: u32,
+}
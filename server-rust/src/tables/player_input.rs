@@ -1,11 +1,32 @@
-use spacetimedb::table;
+use spacetimedb::{table, Timestamp};
 
 // See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
 #[table(name = player_input, public)]
+#[derive(Clone)]
 pub struct PlayerInput {
     #[primary_key]
     pub player_id: u32,
+    #[index(btree)]
+    pub entity_id: u32,             // Entity driven by this input, for joining against entity cells
     pub horizontal_axis: f32,       // -1.0 to 1.0 (A/D keys), clamped server-side
     pub jump_pressed: bool,         // Space key state
-    pub input_sequence: u32,        // Anti-cheat sequence numbering for input validation
+    pub input_sequence: u32,        // Client-assigned sequence number of the last applied command
+    pub client_tick: u32,           // Client-side tick the last applied command was sampled on
+}
+
+// Queued input commands, consumed one per physics tick in sequence order
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = input_command)]
+pub struct InputCommand {
+    #[primary_key]
+    #[auto_inc]
+    pub command_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub sequence: u32,              // Client-assigned, strictly increasing per player
+    pub client_tick: u32,           // Client-side tick the command was sampled on
+    pub horizontal_axis: f32,       // -1.0 to 1.0, clamped server-side
+    pub jump_held: bool,            // Space key state when sampled
+    pub jump_pressed: bool,         // Space was pressed since the previous command
+    pub received_at: Timestamp,
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ProjectileKind {
+    Arrow,
+    ThrowingStar,
+    MagicBolt,
+}
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = projectile, public)]
+pub struct Projectile {
+    #[primary_key]
+    pub entity_id: u32,
+    pub kind: ProjectileKind,
+    #[index(btree)]
+    pub owner_entity_id: u32,       // Entity that fired the projectile (never hit by it)
+    pub damage: u32,                // Damage payload delivered on each hit, added to the owner's attack
+    pub damage_multiplier: f32,     // Skill multiplier applied to the whole hit
+    pub skill_id: Option<u32>,      // Skill that fired it; its target status effect is applied on hit
+    pub pierce_remaining: u32,      // Extra targets the projectile passes through before despawning
+    pub expires_at: Timestamp,      // Despawned once the lifetime runs out
+}
@@ -0,0 +1,11 @@
+use spacetimedb::table;
+use crate::types::DbVector2;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = prop, public)]
+pub struct Prop {
+    #[primary_key]
+    pub entity_id: u32,
+    pub prop_id: u32,               // Which decoration/obstacle this is, for the client to render
+    pub size: DbVector2,            // Collider extents in world units
+}
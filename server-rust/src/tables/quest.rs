@@ -0,0 +1,14 @@
+use spacetimedb::{table, Timestamp};
+
+// Quests a character has finished; used as prerequisites such as job advancement
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = quest_completion, public)]
+pub struct QuestCompletion {
+    #[primary_key]
+    #[auto_inc]
+    pub completion_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub quest_id: u32,
+    pub completed_at: Timestamp,
+}
@@ -0,0 +1,26 @@
+use spacetimedb::table;
+
+// A shop run by an NPC; players must stand near one of that NPC's entities to use it
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = shop, public)]
+pub struct Shop {
+    #[primary_key]
+    pub shop_id: u32,
+    #[unique]
+    pub npc_id: u32,
+    pub name: String,
+    pub recharge_price: u64,        // Mesos per unit to refill rechargeable items, 0 if this shop does not recharge
+}
+
+// An item for sale in a shop
+#[table(name = shop_item, public)]
+pub struct ShopItem {
+    #[primary_key]
+    #[auto_inc]
+    pub shop_item_id: u32,
+    #[index(btree)]
+    pub shop_id: u32,
+    pub item_id: u32,
+    pub price: u64,                 // Mesos per unit
+    pub stock: Option<u32>,         // Units left, None for unlimited
+}
@@ -0,0 +1,66 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+use crate::tables::{ProjectileKind, StatusEffectSpec};
+use crate::types::DbVector2;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum SkillShape {
+    None,                           // No damage (movement or utility skills)
+    Melee,                          // Box of `range` size, `range_offset` in front of the caster
+    Projectile(ProjectileKind),     // Fires `hit_count` projectiles that pierce up to `max_targets`
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum SkillMovement {
+    None,
+    Dash(f32),                      // Horizontal launch speed in the facing direction
+    Teleport(f32),                  // Instant horizontal distance in the facing direction
+}
+
+// Data-driven skill definitions
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = skill, public)]
+pub struct Skill {
+    #[primary_key]
+    pub skill_id: u32,
+    pub name: String,
+    #[index(btree)]
+    pub job_id: u32,                    // Learnable by this job and every job advanced from it
+    pub max_level: u32,
+    pub damage_multiplier: f32,         // At skill level 1
+    pub damage_multiplier_per_level: f32,
+    pub hit_count: u32,                 // Damage lines per target
+    pub max_targets: u32,
+    pub shape: SkillShape,
+    pub range: DbVector2,               // Hitbox width and height for melee skills
+    pub range_offset: f32,
+    pub mp_cost: u32,
+    pub cooldown_ms: u64,               // Before this skill can be used again
+    pub animation_lock_ms: u64,         // Before any attack or skill can be used again
+    pub movement: SkillMovement,
+    pub status_effect: Option<StatusEffectSpec>,
+}
+
+// Skill levels a character has learned
+#[table(name = character_skill, public)]
+pub struct CharacterSkill {
+    #[primary_key]
+    #[auto_inc]
+    pub character_skill_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub skill_id: u32,
+    pub level: u32,
+}
+
+// When a character may use a skill again
+#[table(name = skill_cooldown, public)]
+pub struct SkillCooldown {
+    #[primary_key]
+    #[auto_inc]
+    pub cooldown_id: u64,
+    #[index(btree)]
+    pub player_id: u32,
+    pub skill_id: u32,
+    pub ready_at: Timestamp,
+}
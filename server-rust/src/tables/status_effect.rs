@@ -0,0 +1,57 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum StatusEffectKind {
+    SpeedUp,    // Movement speed +magnitude per stack (0.2 = +20%)
+    JumpUp,     // Jump force +magnitude per stack
+    AttackUp,   // Attack +magnitude per stack
+    Poison,     // Loses magnitude HP per stack every POISON_TICK_MS; never kills
+    Slow,       // Movement speed -magnitude per stack
+    Stun,       // No movement, attacks or skills
+    Freeze,     // No movement, attacks or skills
+}
+
+// What happens when an effect is applied to an entity that already has the same kind
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum StackingRule {
+    Refresh,        // Keep one instance: restart its duration and keep the stronger magnitude
+    Stack(u32),     // Keep one instance: add a stack up to the given maximum and restart its duration
+    Independent,    // Every application is its own instance with its own expiry
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum EffectTarget {
+    Caster,
+    Targets,        // Whoever the skill hits
+}
+
+// How a skill or item applies an effect
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub struct StatusEffectSpec {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub duration_ms: u64,
+    pub stacking: StackingRule,
+    pub target: EffectTarget,
+}
+
+// An active effect on an entity
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = status_effect, public)]
+pub struct StatusEffect {
+    #[primary_key]
+    #[auto_inc]
+    pub effect_id: u64,
+    #[index(btree)]
+    pub entity_id: u32,
+    pub kind: StatusEffectKind,
+    pub source_entity_id: Option<u32>,
+    pub source_skill_id: Option<u32>,
+    pub magnitude: f32,
+    pub stacks: u32,
+    pub stacking: StackingRule,
+    pub applied_at: Timestamp,
+    pub expires_at: Timestamp,
+    pub next_tick_at: Timestamp,        // Next damage tick for Poison
+}
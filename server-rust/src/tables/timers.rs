@@ -6,3 +6,75 @@ pub struct PhysicsStepTimer {
     pub scheduled_id: u64,
     pub scheduled_at: ScheduleAt,
 }
+
+#[spacetimedb::table(name = suspicious_activity_timer, scheduled(crate::flush_suspicious_activity))]
+pub struct SuspiciousActivityTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = monster_spawn_timer, scheduled(crate::monster_spawn_tick))]
+pub struct MonsterSpawnTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = combat_event_cleanup_timer, scheduled(crate::cleanup_combat_events))]
+pub struct CombatEventCleanupTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = item_drop_expiry_timer, scheduled(crate::expire_item_drops))]
+pub struct ItemDropExpiryTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = trade_check_timer, scheduled(crate::check_trades))]
+pub struct TradeCheckTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = chat_cleanup_timer, scheduled(crate::cleanup_chat_messages))]
+pub struct ChatCleanupTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = party_refresh_timer, scheduled(crate::refresh_parties))]
+pub struct PartyRefreshTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = guild_invite_cleanup_timer, scheduled(crate::expire_guild_invites))]
+pub struct GuildInviteCleanupTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = status_effect_timer, scheduled(crate::status_effect_tick))]
+pub struct StatusEffectTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
@@ -0,0 +1,80 @@
+use spacetimedb::{table, SpacetimeType, Timestamp};
+use crate::tables::InventoryTab;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/derive.SpacetimeType.html for custom types
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum TradeState {
+    Invited,                        // Waiting for the partner to accept
+    Open,                           // Both sides may change their offers until they lock
+    Locked,                         // Both offers are final; waiting for both confirmations
+}
+
+// A trade between two players; finished or cancelled trades are deleted and logged
+// Private; each trader reads their own trades through the my_trades view.
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.table.html for table definitions
+#[table(name = trade_session)]
+pub struct TradeSession {
+    #[primary_key]
+    #[auto_inc]
+    pub trade_id: u64,
+    #[index(btree)]
+    pub initiator_player_id: u32,
+    #[index(btree)]
+    pub partner_player_id: u32,
+    pub state: TradeState,
+    pub initiator_mesos: u64,
+    pub partner_mesos: u64,
+    pub initiator_locked: bool,
+    pub partner_locked: bool,
+    pub initiator_confirmed: bool,
+    pub partner_confirmed: bool,
+    pub created_at: Timestamp,
+}
+
+// An inventory stack one side has put up for trade
+// Items stay in the inventory until the trade executes, where the slot is checked again.
+// Private; both traders read the offers through the my_trade_offers view.
+#[table(name = trade_offer)]
+pub struct TradeOffer {
+    #[primary_key]
+    #[auto_inc]
+    pub offer_id: u64,
+    #[index(btree)]
+    pub trade_id: u64,
+    pub player_id: u32,
+    pub tab: InventoryTab,
+    pub slot: u32,
+    pub item_id: u32,
+    pub quantity: u32,
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub struct TradedItem {
+    pub item_id: u32,
+    pub quantity: u32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub enum TradeOutcome {
+    Completed,
+    Cancelled(String),              // Reason, such as who cancelled or why the trade was aborted
+}
+
+// Record of every finished or cancelled trade, kept private for support staff
+#[table(name = trade_log)]
+pub struct TradeLog {
+    #[primary_key]
+    #[auto_inc]
+    pub log_id: u64,
+    pub trade_id: u64,
+    #[index(btree)]
+    pub initiator_player_id: u32,
+    #[index(btree)]
+    pub partner_player_id: u32,
+    pub initiator_items: Vec<TradedItem>,
+    pub partner_items: Vec<TradedItem>,
+    pub initiator_mesos: u64,
+    pub partner_mesos: u64,
+    pub outcome: TradeOutcome,
+    pub logged_at: Timestamp,
+}
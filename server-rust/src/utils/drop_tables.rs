@@ -0,0 +1,175 @@
+use spacetimedb::rand::Rng;
+use spacetimedb::ReducerContext;
+use crate::config::*;
+use crate::tables::{DropEntry, DropKind, DropRule};
+use crate::utils::RandomExtensions;
+// Import table access traits
+use crate::tables::loot::drop_entry;
+
+// Where drop table entries are read from
+// The database in reducers; a plain slice of entries when rolling with a seeded RNG.
+pub trait DropTableSource {
+    fn drop_entries(&self, table_id: u32) -> Vec<DropEntry>;
+}
+
+impl DropTableSource for ReducerContext {
+    fn drop_entries(&self, table_id: u32) -> Vec<DropEntry> {
+        self.db.drop_entry().table_id().filter(table_id).collect()
+    }
+}
+
+impl DropTableSource for [DropEntry] {
+    fn drop_entries(&self, table_id: u32) -> Vec<DropEntry> {
+        self.iter().filter(|entry| entry.table_id == table_id).cloned().collect()
+    }
+}
+
+// Roll a drop table once: (item_id, quantity) per drop, with MESO_ITEM_ID for mesos
+// Guaranteed entries always drop, Chance entries roll independently and one Weighted entry is picked.
+pub fn roll_drop_table<R, S>(rng: &mut R, source: &S, table_id: u32) -> Vec<(u32, u32)>
+where
+    R: Rng + ?Sized,
+    S: DropTableSource + ?Sized,
+{
+    let mut drops = Vec::new();
+    roll_into(rng, source, table_id, 0, &mut drops);
+    drops
+}
+
+fn roll_into<R, S>(rng: &mut R, source: &S, table_id: u32, depth: u32, drops: &mut Vec<(u32, u32)>)
+where
+    R: Rng + ?Sized,
+    S: DropTableSource + ?Sized,
+{
+    // Guards against tables that nest themselves
+    if depth > MAX_DROP_TABLE_DEPTH {
+        log::warn!("Drop table {} is nested deeper than {}", table_id, MAX_DROP_TABLE_DEPTH);
+        return;
+    }
+
+    let entries = source.drop_entries(table_id);
+    let mut weighted = Vec::new();
+    for entry in &entries {
+        match entry.rule {
+            DropRule::Guaranteed => resolve_entry(rng, source, entry, depth, drops),
+            DropRule::Chance(probability) => {
+                if rng.chance(probability) {
+                    resolve_entry(rng, source, entry, depth, drops);
+                }
+            }
+            DropRule::Weighted(weight) => weighted.push((entry, weight)),
+        }
+    }
+
+    let weights: Vec<u32> = weighted.iter().map(|(_, weight)| *weight).collect();
+    if let Some(index) = rng.weighted_index(&weights) {
+        resolve_entry(rng, source, weighted[index].0, depth, drops);
+    }
+}
+
+fn resolve_entry<R, S>(rng: &mut R, source: &S, entry: &DropEntry, depth: u32, drops: &mut Vec<(u32, u32)>)
+where
+    R: Rng + ?Sized,
+    S: DropTableSource + ?Sized,
+{
+    let quantity = rng.range_u32_inclusive(entry.min_quantity, entry.max_quantity);
+    if quantity == 0 {
+        return;
+    }
+
+    match entry.kind {
+        DropKind::Item(item_id) => drops.push((item_id, quantity)),
+        DropKind::Mesos => drops.push((MESO_ITEM_ID, quantity)),
+        DropKind::Table(nested_table_id) => {
+            for _ in 0..quantity {
+                roll_into(rng, source, nested_table_id, depth + 1, drops);
+            }
+        }
+        DropKind::Nothing => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::seeded_rng;
+
+    fn entry(table_id: u32, kind: DropKind, rule: DropRule, quantity: (u32, u32)) -> DropEntry {
+        DropEntry {
+            entry_id: 0,
+            table_id,
+            kind,
+            rule,
+            min_quantity: quantity.0,
+            max_quantity: quantity.1,
+        }
+    }
+
+    #[test]
+    fn guaranteed_entries_always_drop() {
+        let entries = [entry(1, DropKind::Item(2000000), DropRule::Guaranteed, (2, 2))];
+
+        for seed in 0..100 {
+            let drops = roll_drop_table(&mut seeded_rng(seed), &entries[..], 1);
+            assert_eq!(drops, vec![(2000000, 2)]);
+        }
+    }
+
+    #[test]
+    fn weighted_pick_honours_nothing() {
+        let only_nothing = [entry(1, DropKind::Nothing, DropRule::Weighted(10), (1, 1))];
+        for seed in 0..100 {
+            assert!(roll_drop_table(&mut seeded_rng(seed), &only_nothing[..], 1).is_empty());
+        }
+
+        let entries = [
+            entry(1, DropKind::Nothing, DropRule::Weighted(1), (1, 1)),
+            entry(1, DropKind::Item(4000019), DropRule::Weighted(1), (1, 1)),
+        ];
+        let outcomes: Vec<Vec<(u32, u32)>> = (0..200)
+            .map(|seed| roll_drop_table(&mut seeded_rng(seed), &entries[..], 1))
+            .collect();
+        assert!(outcomes.iter().all(|drops| drops.is_empty() || drops == &vec![(4000019, 1)]));
+        assert!(outcomes.iter().any(|drops| drops.is_empty()));
+        assert!(outcomes.iter().any(|drops| !drops.is_empty()));
+    }
+
+    #[test]
+    fn nested_table_is_rolled_quantity_times() {
+        let entries = [
+            entry(1, DropKind::Table(2), DropRule::Guaranteed, (3, 3)),
+            entry(2, DropKind::Mesos, DropRule::Guaranteed, (10, 10)),
+        ];
+
+        let drops = roll_drop_table(&mut seeded_rng(7), &entries[..], 1);
+        assert_eq!(drops, vec![(MESO_ITEM_ID, 10); 3]);
+    }
+
+    #[test]
+    fn depth_cap_stops_self_nesting_table() {
+        let entries = [
+            entry(1, DropKind::Item(4000001), DropRule::Guaranteed, (1, 1)),
+            entry(1, DropKind::Table(1), DropRule::Guaranteed, (1, 1)),
+        ];
+
+        // One drop at the top level plus one per nested level up to the cap
+        let drops = roll_drop_table(&mut seeded_rng(0), &entries[..], 1);
+        assert_eq!(drops.len(), MAX_DROP_TABLE_DEPTH as usize + 1);
+    }
+
+    #[test]
+    fn same_seed_gives_same_drops() {
+        let entries = [
+            entry(1, DropKind::Item(2000000), DropRule::Chance(0.5), (1, 3)),
+            entry(1, DropKind::Mesos, DropRule::Weighted(3), (5, 20)),
+            entry(1, DropKind::Nothing, DropRule::Weighted(1), (1, 1)),
+        ];
+
+        for seed in 0..20 {
+            assert_eq!(
+                roll_drop_table(&mut seeded_rng(seed), &entries[..], 1),
+                roll_drop_table(&mut seeded_rng(seed), &entries[..], 1),
+            );
+        }
+    }
+}
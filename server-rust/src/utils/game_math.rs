@@ -1,4 +1,5 @@
 use crate::config::*;
+use crate::types::DbVector2;
 
 pub struct GameMath;
 
@@ -7,6 +8,37 @@ impl GameMath {
         (mass as f32).sqrt()
     }
 
+    // Area-of-interest grid cell containing a world position
+    pub fn position_to_cell(position: DbVector2) -> (i32, i32) {
+        (
+            (position.x / AOI_CELL_SIZE).floor() as i32,
+            (position.y / AOI_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    // EXP needed to advance from `level` to the next level
+    // Gentle for the first levels, then roughly cubic.
+    pub fn exp_to_next_level(level: u32) -> u64 {
+        let level = level as u64;
+        15 + level * level * level / 2 + level * 10
+    }
+
+    // EXP one party member receives from a kill worth `exp`
+    // Each member beyond the first adds PARTY_EXP_BONUS_PER_MEMBER to the pool, which is then
+    // split by level or evenly depending on PARTY_EXP_BY_LEVEL.
+    pub fn party_exp_share(exp: u64, member_level: u32, level_sum: u32, member_count: usize) -> u64 {
+        if member_count == 0 || level_sum == 0 {
+            return 0;
+        }
+        let pool = exp as f64 * (1.0 + PARTY_EXP_BONUS_PER_MEMBER as f64 * (member_count - 1) as f64);
+        let share = if PARTY_EXP_BY_LEVEL {
+            pool * member_level as f64 / level_sum as f64
+        } else {
+            pool / member_count as f64
+        };
+        share.round() as u64
+    }
+
     // DEPRECATED: Movement speed is now handled by platformer physics
     // This is kept for compatibility but should be removed in future updates
     pub fn mass_to_max_move_speed(_mass: u32) -> f32 {
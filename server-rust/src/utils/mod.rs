@@ -1,7 +1,11 @@
 pub mod game_math;
 pub mod collision_detection;
 pub mod random_extensions;
+pub mod drop_tables;
+pub mod word_filter;
 
 pub use game_math::*;
 pub use collision_detection::*;
-pub use random_extensions::*;
\ No newline at end of file
+pub use random_extensions::*;
+pub use drop_tables::*;
+pub use word_filter::*;
\ No newline at end of file
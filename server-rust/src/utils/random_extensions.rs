@@ -1,25 +1,79 @@
-use spacetimedb::StdbRng;
+use spacetimedb::rand::{Rng, SeedableRng};
+use spacetimedb::rand::rngs::StdRng;
 
-// Extensions for SpacetimeDB's built-in random number generator
+// Extensions for random number generators
+// Implemented for any `Rng`, including SpacetimeDB's built-in one, so they work on `ctx.rng()` directly.
 pub trait RandomExtensions {
     fn range_f32(&mut self, min: f32, max: f32) -> f32;
     fn range_u32(&mut self, min: u32, max: u32) -> u32;
     fn range_u64(&mut self, min: u64, max: u64) -> u64;
+    fn range_u32_inclusive(&mut self, min: u32, max: u32) -> u32;
+    fn chance(&mut self, probability: f32) -> bool;
+    fn weighted_index(&mut self, weights: &[u32]) -> Option<usize>;
 }
 
-impl RandomExtensions for StdbRng {
+impl<R: Rng + ?Sized> RandomExtensions for R {
     fn range_f32(&mut self, min: f32, max: f32) -> f32 {
-        use spacetimedb::rand::Rng;
         self.gen::<f32>() * (max - min) + min
     }
 
     fn range_u32(&mut self, min: u32, max: u32) -> u32 {
-        use spacetimedb::rand::Rng;
         self.gen_range(min..max)
     }
-    
+
     fn range_u64(&mut self, min: u64, max: u64) -> u64 {
-        use spacetimedb::rand::Rng;
         self.gen_range(min..max)
     }
-}
\ No newline at end of file
+
+    // Both ends included, in either order; used for quantity ranges such as 1..=3 potions
+    fn range_u32_inclusive(&mut self, min: u32, max: u32) -> u32 {
+        self.gen_range(min.min(max)..=max.max(min))
+    }
+
+    // True with the given probability; values outside 0..=1 are clamped
+    fn chance(&mut self, probability: f32) -> bool {
+        self.gen::<f32>() < probability.clamp(0.0, 1.0)
+    }
+
+    // Index picked with probability proportional to its weight; None if every weight is 0
+    fn weighted_index(&mut self, weights: &[u32]) -> Option<usize> {
+        let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = self.gen_range(0..total);
+        weights.iter().position(|&weight| {
+            if roll < weight as u64 {
+                return true;
+            }
+            roll -= weight as u64;
+            false
+        })
+    }
+}
+
+// Deterministic generator for reproducing rolls outside a reducer, such as in unit tests
+// Reducers should keep using `ctx.rng()`, which SpacetimeDB seeds per transaction.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_index_is_none_without_weight() {
+        let mut rng = seeded_rng(0);
+        assert_eq!(rng.weighted_index(&[]), None);
+        assert_eq!(rng.weighted_index(&[0, 0, 0]), None);
+    }
+
+    #[test]
+    fn weighted_index_skips_zero_weights() {
+        for seed in 0..100 {
+            assert_eq!(seeded_rng(seed).weighted_index(&[0, 5, 0]), Some(1));
+        }
+    }
+}
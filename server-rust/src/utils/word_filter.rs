@@ -0,0 +1,39 @@
+// Pluggable chat word filtering
+// Chat calls the filter through this trait, so a different implementation can be swapped in
+// without touching the chat reducers (see `word_filter` in chat_reducers.rs).
+pub trait WordFilter {
+    // Returns the text with blocked words masked
+    fn filter(&self, text: &str) -> String;
+}
+
+// Masks every case-insensitive occurrence of a blocked word with asterisks
+pub struct BlocklistFilter {
+    words: &'static [&'static str],
+}
+
+impl BlocklistFilter {
+    pub const fn new(words: &'static [&'static str]) -> Self {
+        Self { words }
+    }
+}
+
+impl WordFilter for BlocklistFilter {
+    fn filter(&self, text: &str) -> String {
+        let mut chars: Vec<char> = text.chars().collect();
+        let lowered: Vec<char> = chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+
+        for word in self.words {
+            let blocked: Vec<char> = word.chars().map(|c| c.to_ascii_lowercase()).collect();
+            if blocked.is_empty() || blocked.len() > lowered.len() {
+                continue;
+            }
+            for start in 0..=lowered.len() - blocked.len() {
+                if lowered[start..start + blocked.len()] == blocked[..] {
+                    chars[start..start + blocked.len()].fill('*');
+                }
+            }
+        }
+
+        chars.into_iter().collect()
+    }
+}
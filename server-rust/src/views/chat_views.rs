@@ -0,0 +1,46 @@
+use spacetimedb::{view, ViewContext};
+use crate::tables::{ChatChannel, ChatMessage};
+// Import table access traits
+use crate::tables::chat::chat_message__view;
+use crate::tables::entity::entity__view;
+use crate::tables::guild::guild_member__view;
+use crate::tables::party::party_member__view;
+use crate::tables::player::{player__view, player_entity__view};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.view.html for view definitions
+
+// Recent messages the caller may read: their current map, whispers to or from them,
+// their party and guild, and the world channel
+#[view(name = my_chat_messages, public)]
+pub fn my_chat_messages(ctx: &ViewContext) -> Vec<ChatMessage> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Vec::new();
+    };
+    let player_id = player.player_id;
+    let map_id = ctx.db.player_entity().player_id().filter(player_id)
+        .find_map(|owned| ctx.db.entity().entity_id().find(owned.entity_id))
+        .map(|entity| entity.map_id);
+    let party_id = ctx.db.party_member().player_id().find(player_id).map(|member| member.party_id);
+    let guild_id = ctx.db.guild_member().player_id().find(player_id).map(|member| member.guild_id);
+
+    let messages = ctx.db.chat_message();
+    let in_channel = |channel: ChatChannel| move |message: &ChatMessage| message.channel == channel;
+
+    // World messages have no scope, so they are the map_id 0 rows on the World channel
+    let mut visible: Vec<ChatMessage> = messages.map_id().filter(0u32)
+        .filter(in_channel(ChatChannel::World))
+        .chain(messages.sender_player_id().filter(player_id).filter(in_channel(ChatChannel::Whisper)))
+        .chain(messages.recipient_player_id().filter(player_id).filter(in_channel(ChatChannel::Whisper)))
+        .collect();
+    if let Some(map_id) = map_id {
+        visible.extend(messages.map_id().filter(map_id).filter(in_channel(ChatChannel::Map)));
+    }
+    if let Some(party_id) = party_id {
+        visible.extend(messages.party_id().filter(party_id).filter(in_channel(ChatChannel::Party)));
+    }
+    if let Some(guild_id) = guild_id {
+        visible.extend(messages.guild_id().filter(guild_id).filter(in_channel(ChatChannel::Guild)));
+    }
+    visible.sort_by_key(|message| message.message_id);
+    visible
+}
@@ -0,0 +1,27 @@
+use spacetimedb::{view, ViewContext};
+use crate::tables::{Friend, FriendRequest};
+// Import table access traits
+use crate::tables::player::player__view;
+use crate::tables::friend::{friend__view, friend_request__view};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.view.html for view definitions
+
+// The caller's buddy list
+#[view(name = my_friends, public)]
+pub fn my_friends(ctx: &ViewContext) -> Vec<Friend> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Vec::new();
+    };
+    ctx.db.friend().player_id().filter(player.player_id).collect()
+}
+
+// Pending requests the caller sent or received
+#[view(name = my_friend_requests, public)]
+pub fn my_friend_requests(ctx: &ViewContext) -> Vec<FriendRequest> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Vec::new();
+    };
+    ctx.db.friend_request().requester_player_id().filter(player.player_id)
+        .chain(ctx.db.friend_request().target_player_id().filter(player.player_id))
+        .collect()
+}
@@ -0,0 +1,16 @@
+use spacetimedb::{view, ViewContext};
+use crate::tables::InventoryItem;
+// Import table access traits
+use crate::tables::item::inventory_item__view;
+use crate::tables::player::player__view;
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.view.html for view definitions
+
+// The caller's own inventory; the inventory_item table itself is private
+#[view(name = my_inventory, public)]
+pub fn my_inventory(ctx: &ViewContext) -> Vec<InventoryItem> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Vec::new();
+    };
+    ctx.db.inventory_item().player_id().filter(player.player_id).collect()
+}
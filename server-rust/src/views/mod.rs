@@ -0,0 +1,9 @@
+pub mod inventory_views;
+pub mod chat_views;
+pub mod trade_views;
+pub mod friend_views;
+
+pub use inventory_views::*;
+pub use chat_views::*;
+pub use trade_views::*;
+pub use friend_views::*;
@@ -0,0 +1,26 @@
+use spacetimedb::{view, ViewContext};
+use crate::tables::{TradeOffer, TradeSession};
+// Import table access traits
+use crate::tables::player::player__view;
+use crate::tables::trade::{trade_session__view, trade_offer__view};
+
+// See: https://docs.rs/spacetimedb/latest/spacetimedb/attr.view.html for view definitions
+
+// Trades the caller is part of, as initiator or partner
+#[view(name = my_trades, public)]
+pub fn my_trades(ctx: &ViewContext) -> Vec<TradeSession> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Vec::new();
+    };
+    ctx.db.trade_session().initiator_player_id().filter(player.player_id)
+        .chain(ctx.db.trade_session().partner_player_id().filter(player.player_id))
+        .collect()
+}
+
+// Both sides' offers in the caller's trades, so each trader sees what they would receive
+#[view(name = my_trade_offers, public)]
+pub fn my_trade_offers(ctx: &ViewContext) -> Vec<TradeOffer> {
+    my_trades(ctx).into_iter()
+        .flat_map(|trade| ctx.db.trade_offer().trade_id().filter(trade.trade_id))
+        .collect()
+}